@@ -1,28 +1,165 @@
 // Chamon application state
+use crate::baseline::ScanControl;
+use crate::browse::{BaselineFiles, ChangeFilter, Row};
+use crate::compare::{ChangeKind, Comparison, FileState};
 use crate::config::Config;
-use std::path::PathBuf;
-use std::sync::Arc;
+use crate::report::ReportFormat;
+use crate::search::{HitSource, SearchMode, SearchResults};
+use crate::config_watch::ConfigWatcher;
+use crate::history::FileHistory;
+use crate::index::{BaselineIndex, DeleteImpact, IndexEntry};
+use crate::journal::Journal;
+use crate::packages::PackageDb;
+use crate::state::AppState;
+use crate::summary::DriftSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use tui_components::{HitMap, Toast};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// How long a toast stays on screen
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ViewMode {
     Dashboard,
     Changes,
     Baselines,
+    Timeline,
 }
 
 #[derive(Debug, Clone)]
 pub struct BaselineItem {
     pub version: String,
+    pub filename: String,
     pub file_count: usize,
+    pub size_bytes: u64,
+    pub created_at: u64,
+    pub parent: Option<String>,
+    pub label: Option<String>,
+    pub notes: Option<String>,
+    pub host: String,
+    pub pinned: bool,
     pub is_initial: bool,
+    /// Imported from another machine to compare against (see archive.rs)
+    pub is_reference: bool,
     pub is_active: bool,
 }
 
+impl BaselineItem {
+    fn from_entry(entry: &IndexEntry) -> Self {
+        Self {
+            version: if entry.is_delta || entry.reference {
+                entry.version.clone()
+            } else {
+                "Initial Baseline".to_string()
+            },
+            filename: entry.filename.clone(),
+            file_count: entry.file_count,
+            size_bytes: entry.size_bytes,
+            created_at: entry.created_at,
+            parent: entry.parent.clone(),
+            label: entry.label.clone(),
+            notes: entry.notes.clone(),
+            host: entry.host.clone(),
+            pinned: entry.pinned,
+            is_initial: !entry.is_delta && !entry.reference,
+            is_reference: entry.reference,
+            is_active: false, // Will be set based on active_baseline index
+        }
+    }
+}
+
+/// Trimmed input, or None when the field was cleared
+fn non_empty(input: &str) -> Option<String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Build the Baselines list from the index: deltas first (newest first),
+/// then the initial baseline, then references from other machines
+fn load_baseline_items(data_dir: &Path) -> Vec<BaselineItem> {
+    let index = BaselineIndex::load_or_rebuild(data_dir).unwrap_or_default();
+    let mut baselines: Vec<BaselineItem> = index.entries.iter().map(BaselineItem::from_entry).collect();
+    
+    let rank = |b: &BaselineItem| (b.is_reference, b.is_initial);
+    baselines.sort_by(|a, b| rank(a).cmp(&rank(b))
+        .then_with(|| b.created_at.cmp(&a.created_at))
+        .then_with(|| b.version.cmp(&a.version)));
+    
+    baselines
+}
+
+/// Active baseline: first delta, or initial if there are no deltas
+fn default_active_baseline(baselines: &[BaselineItem]) -> usize {
+    baselines
+        .iter()
+        .position(|b| !b.is_initial && !b.is_reference)
+        .or_else(|| baselines.iter().position(|b| b.is_initial))
+        .unwrap_or(0)
+}
+
 // Use shared popup component
 use tui_components::Popup;
 
+/// What the currently open popup is asking about, so confirming it knows
+/// which action to run and on which baseline
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingAction {
+    /// Input popup editing a baseline's label
+    EditLabel { filename: String },
+    /// Input popup editing a baseline's notes
+    EditNotes { filename: String },
+    /// Confirm popup for applying the retention policy
+    Prune,
+    /// Confirm popup for deleting a baseline nothing depends on
+    DeleteBaseline { filename: String },
+    /// Confirm popup for deleting a delta by merging it into its successor
+    MergeDeleteBaseline { filename: String },
+    /// Confirm popup for stopping the running baseline scan
+    CancelScan,
+    /// Input popup for the Changes view's path filter
+    FilterPath,
+    /// Input popup for the Changes view's package filter
+    FilterPackage,
+    /// Input popup for a path or content search
+    Search { mode: SearchMode },
+    /// Input popup for the path to show the history of
+    History,
+}
+
+/// A list or scrollable pane the mouse can act on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pane {
+    Baselines,
+    BaselineFiles,
+    BaselineViewer,
+    Changes,
+    ChangeDetails,
+    Timeline,
+    SearchResults,
+    HistorySteps,
+    HistoryDetails,
+}
+
+/// What was drawn under a screen cell in the last frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hit {
+    Tab(ViewMode),
+    /// A row of a list pane, by the index its selection uses
+    Row(Pane, usize),
+    /// Anywhere in a pane, for the scroll wheel
+    Pane(Pane),
+    /// A popup button (0 = Yes, 1 = No)
+    PopupButton(usize),
+}
+
 /// Progress update messages sent from worker threads to main thread
 #[derive(Debug, Clone)]
 pub enum ProgressUpdate {
@@ -40,10 +177,128 @@ pub enum ProgressUpdate {
     PhaseChanged { phase: String },
     /// Baseline creation completed
     BaselineCompleted { success: bool, message: String },
+    /// Baseline creation was cancelled and nothing was saved
+    BaselineCancelled,
+}
+
+/// A comparison against the live system running on a background thread
+pub struct LiveCompare {
+    control: ScanControl,
+    rx: mpsc::Receiver<std::io::Result<Comparison>>,
+    thread: std::thread::JoinHandle<()>,
+    /// The baseline being compared with
+    pub filename: String,
+    pub started_at: SystemTime,
+}
+
+impl LiveCompare {
+    fn start(data_dir: PathBuf, filename: String, config: crate::config::BaselineConfig) -> Self {
+        let control = ScanControl::new();
+        let (tx, rx) = mpsc::channel();
+        let worker_control = control.clone();
+        let worker_filename = filename.clone();
+        let thread = std::thread::spawn(move || {
+            let _ = tx.send(crate::compare::compare_to_live(&data_dir, &worker_filename, &config, worker_control));
+        });
+        Self { control, rx, thread, filename, started_at: SystemTime::now() }
+    }
+    
+    /// The result once the scan has finished; the thread is joined then
+    fn finish(self) -> Result<std::io::Result<Comparison>, Self> {
+        let result = match self.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return Err(self),
+            Err(mpsc::TryRecvError::Disconnected) => Err(std::io::Error::other("Live scan stopped unexpectedly")),
+        };
+        let _ = self.thread.join();
+        Ok(result)
+    }
+    
+    fn stop(self) {
+        self.control.cancel();
+        let _ = self.thread.join();
+    }
+}
+
+/// A comparison of two stored baselines running on a background thread,
+/// since it materializes both chains
+pub struct StoredCompare {
+    rx: mpsc::Receiver<std::io::Result<Comparison>>,
+    thread: std::thread::JoinHandle<()>,
+    /// Switch to the Changes view once it's done
+    pub open_changes: bool,
+    /// Show the result side by side (two marked baselines)
+    pub split: bool,
+}
+
+impl StoredCompare {
+    fn start(data_dir: PathBuf, from: String, to: String) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            let _ = tx.send(crate::compare::compare_baselines(&data_dir, &from, &to));
+        });
+        Self { rx, thread, open_changes: false, split: false }
+    }
+    
+    /// The result once the comparison has finished; the thread is joined then
+    fn finish(self) -> Result<std::io::Result<Comparison>, Self> {
+        let result = match self.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return Err(self),
+            Err(mpsc::TryRecvError::Disconnected) => Err(std::io::Error::other("Comparison stopped unexpectedly")),
+        };
+        let _ = self.thread.join();
+        Ok(result)
+    }
+    
+    /// Wait for the comparison; it only reads the data dir and can't be
+    /// cancelled part way
+    fn stop(self) {
+        let _ = self.thread.join();
+    }
+}
+
+/// A search of stored baseline content running on a background thread
+pub struct ContentSearch {
+    control: ScanControl,
+    rx: mpsc::Receiver<std::io::Result<SearchResults>>,
+    thread: std::thread::JoinHandle<()>,
+    pub query: String,
+}
+
+impl ContentSearch {
+    fn start(data_dir: PathBuf, query: String) -> Self {
+        let control = ScanControl::new();
+        let (tx, rx) = mpsc::channel();
+        let worker_control = control.clone();
+        let worker_query = query.clone();
+        let thread = std::thread::spawn(move || {
+            let _ = tx.send(crate::search::search_contents(&data_dir, &worker_query, &worker_control));
+        });
+        Self { control, rx, thread, query }
+    }
+    
+    /// The results once the search has finished; the thread is joined then
+    fn finish(self) -> Result<std::io::Result<SearchResults>, Self> {
+        let result = match self.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return Err(self),
+            Err(mpsc::TryRecvError::Disconnected) => Err(std::io::Error::other("Search stopped unexpectedly")),
+        };
+        let _ = self.thread.join();
+        Ok(result)
+    }
+    
+    fn stop(self) {
+        self.control.cancel();
+        let _ = self.thread.join();
+    }
 }
 
 pub struct App {
     pub config: Config,
+    pub config_path: Option<PathBuf>,
+    pub config_watcher: Option<ConfigWatcher>,
     pub data_dir: PathBuf,
     pub current_view: ViewMode,
     pub should_quit: bool,
@@ -53,9 +308,74 @@ pub struct App {
     pub selected_baseline: usize,
     pub active_baseline: usize,
     pub comparison_results: Option<ComparisonResults>,
+    /// The file-level changes behind `comparison_results`
+    pub comparison: Option<Comparison>,
+    /// Baseline rules changed since `comparison_results` was computed
+    pub comparison_stale: bool,
+    /// Baselines marked for a side-by-side comparison (file names, at most two)
+    pub marked_baselines: Vec<String>,
+    /// Files of the selected baseline, while browsing them in the display panel
+    pub baseline_browser: Option<BaselineFiles>,
+    
+    // Changes view state
+    /// Index into `change_rows`
+    pub selected_change: usize,
+    pub change_filter: ChangeFilter,
+    /// Directory tree instead of a flat list
+    pub change_tree: bool,
+    /// Directories folded away in the tree
+    pub collapsed_dirs: HashSet<String>,
+    /// The comparison's changes as currently filtered and laid out
+    pub change_rows: Vec<Row>,
+    pub change_detail_scroll: u16,
+    /// Old and new side of the selected change in two panes
+    pub split_compare: bool,
+    /// Package owners for the comparison's files
+    pub packages: PackageDb,
+    /// Root and database time `packages` was read from; None until loaded
+    pub packages_source: Option<(PathBuf, Option<SystemTime>)>,
+    /// Package database being read in the background
+    pub packages_load: Option<mpsc::Receiver<(PathBuf, Option<SystemTime>, PackageDb)>>,
+    /// Comparison against the live system running in the background
+    pub live_compare: Option<LiveCompare>,
+    /// Comparison of two stored baselines running in the background
+    pub stored_compare: Option<StoredCompare>,
+    
+    /// Change to select in the Changes view once the running live
+    /// comparison finishes
+    pub pending_change_focus: Option<String>,
+    
+    /// Results of the last `/` or `?` search, while they are shown
+    pub search: Option<SearchResults>,
+    /// Content search running in the background
+    pub content_search: Option<ContentSearch>,
+    /// Files of the active baseline (by file name) for path searches, kept
+    /// until the baselines are reloaded
+    pub active_files: Option<(String, FileState)>,
+    /// History of one file across the baselines, while it is shown
+    pub history: Option<FileHistory>,
+    
+    // Timeline state
+    pub timeline: Vec<crate::timeline::Event>,
+    /// Change journal as read so far; synced before use
+    pub journal: Journal,
+    /// Index into `timeline`
+    pub selected_event: usize,
+    
+    // Dashboard state
+    pub drift_summary: Option<DriftSummary>,
+    /// Why the last refresh failed
+    pub dashboard_error: Option<String>,
+    pub dashboard_refresh: Option<LiveCompare>,
+    pub next_dashboard_refresh: Option<SystemTime>,
     
     // Popup state
     pub popup: Option<Popup>,
+    pub pending_action: Option<PendingAction>,
+    pub toasts: Vec<Toast>,
+    
+    /// Clickable regions of the last frame
+    pub hits: HitMap<Hit>,
     
     // Baseline creation progress tracking
     pub creating_baseline: bool,
@@ -67,9 +387,20 @@ pub struct App {
     pub baseline_start_time: Option<SystemTime>,
     pub package_db_progress: Option<(usize, usize)>, // (processed, total)
     pub baseline_phase: String, // "scanning", "packaging", "finalizing"
+    /// When the running scan was paused, if it is
+    pub baseline_paused_at: Option<SystemTime>,
+    /// Time spent paused so far, left out of the elapsed time
+    pub baseline_paused_for: Duration,
+    
+    // Cancel/pause switches and worker thread of the running scan
+    pub scan_control: Option<ScanControl>,
+    pub scan_thread: Option<std::thread::JoinHandle<()>>,
     
     // Channel for receiving progress updates from background thread
     pub progress_rx: Option<mpsc::Receiver<ProgressUpdate>>,
+    
+    /// Tampered baselines in the active chain, from the check started at launch
+    pub integrity_check: Option<mpsc::Receiver<Vec<String>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,115 +414,782 @@ pub struct ComparisonResults {
 }
 
 impl App {
-    pub fn new() -> Self {
-        let config_content = include_str!("../config.yaml");
-        let config: Config = serde_yaml::from_str(config_content)
-            .unwrap_or_else(|_| Config::default());
-        
-        // Use data directory relative to project or in a standard location
-        let data_dir = PathBuf::from("/root/_playground/rust/dev/chamon/data");
-        
-        // Load baselines from disk
-        let mut baselines = Vec::new();
-        let baselines_dir = data_dir.join("baselines");
-        
-        // Load initial baseline if it exists (saved in baselines_dir as baseline-initial.json)
-        let initial_path = baselines_dir.join("baseline-initial.json");
-        if initial_path.exists() {
-            if let Ok(baseline) = crate::baseline::Baseline::load(&data_dir, "baseline-initial.json") {
-                baselines.push(BaselineItem {
-                    version: "Initial Baseline".to_string(),
-                    file_count: baseline.file_count,
-                    is_initial: true,
-                    is_active: false, // Will be set based on active_baseline index
-                });
-            }
-        }
-        
-        // Load delta baselines
-        if baselines_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&baselines_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                        // Skip initial baseline (already loaded)
-                        if filename == "baseline-initial.json" {
-                            continue;
-                        }
-                        
-                        // Load delta baselines (format: baseline-YYYYMMDD-HHMMSS.json)
-                        if filename.starts_with("baseline-") && filename.ends_with(".json") {
-                            if let Ok(baseline) = crate::baseline::Baseline::load(&data_dir, filename) {
-                                // Extract version from filename or use baseline version
-                                let version = baseline.version.clone();
-                                baselines.push(BaselineItem {
-                                    version,
-                                    file_count: baseline.file_count,
-                                    is_initial: false,
-                                    is_active: false, // Will be set based on active_baseline index
-                                });
-                            }
-                        }
-                    }
-                }
+    /// Create the app, loading the layered configuration. `config_path` is
+    /// the `--config` override, if any.
+    pub fn new(config_path: Option<&Path>) -> Self {
+        let loaded = Config::load(config_path);
+        let config = loaded.config.clone();
+        let data_dir = config.data_dir();
+        
+        // Load baselines from the index, then restore the last session
+        let baselines = load_baseline_items(&data_dir);
+        let state = AppState::load(&data_dir);
+        let position = |filename: &Option<String>| {
+            filename.as_ref().and_then(|f| baselines.iter().position(|b| &b.filename == f))
+        };
+        let active_baseline = position(&state.active_baseline)
+            .unwrap_or_else(|| default_active_baseline(&baselines));
+        let selected_baseline = position(&state.selected_baseline).unwrap_or(0);
+        
+        let mut app = Self {
+            config,
+            config_path: config_path.map(Path::to_path_buf),
+            config_watcher: ConfigWatcher::new(
+                Config::search_paths(config_path).into_iter().map(|(path, _)| path).collect(),
+            ).ok(),
+            data_dir,
+            current_view: state.last_view.unwrap_or(ViewMode::Baselines),
+            should_quit: false,
+            baselines,
+            selected_baseline,
+            active_baseline,
+            comparison_results: None,
+            comparison: None,
+            comparison_stale: false,
+            marked_baselines: Vec::new(),
+            baseline_browser: None,
+            selected_change: 0,
+            change_filter: ChangeFilter::default(),
+            change_tree: false,
+            collapsed_dirs: HashSet::new(),
+            change_rows: Vec::new(),
+            change_detail_scroll: 0,
+            split_compare: false,
+            packages: PackageDb::default(),
+            packages_source: None,
+            packages_load: None,
+            live_compare: None,
+            stored_compare: None,
+            pending_change_focus: None,
+            search: None,
+            content_search: None,
+            active_files: None,
+            history: None,
+            timeline: Vec::new(),
+            journal: Journal::default(),
+            selected_event: 0,
+            drift_summary: None,
+            dashboard_error: None,
+            dashboard_refresh: None,
+            next_dashboard_refresh: None,
+            popup: None,
+            pending_action: None,
+            toasts: Vec::new(),
+            hits: HitMap::new(),
+            creating_baseline: false,
+            creating_initial: false,
+            baseline_progress: Vec::new(),
+            baseline_completed: Vec::new(),
+            baseline_files_processed: 0,
+            baseline_estimated_total: None,
+            baseline_start_time: None,
+            package_db_progress: None,
+            baseline_phase: "scanning".to_string(),
+            baseline_paused_at: None,
+            baseline_paused_for: Duration::ZERO,
+            scan_control: None,
+            scan_thread: None,
+            progress_rx: None,
+            integrity_check: None,
+        };
+        app.mark_active_baseline();
+        app.schedule_dashboard_refresh();
+        if app.current_view == ViewMode::Timeline {
+            app.refresh_timeline();
+        }
+        
+        app.start_integrity_check();
+        app.refresh_packages();
+        
+        // Report config problems instead of silently running with defaults
+        let diagnostics = crate::validate::validate(&loaded);
+        if !diagnostics.is_empty() {
+            let details: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+            app.popup = Some(tui_components::Popup::error(
+                "Configuration Problems".to_string(),
+                format!("{}\n\nRun `chamon config check` for details.", details.join("\n")),
+            ));
+        }
+        app
+    }
+    
+    /// Re-read the baseline list from the index, keeping the selection in range.
+    /// The active baseline stays the same file if it still exists, otherwise
+    /// a new one is elected.
+    pub fn reload_baselines(&mut self) {
+        let active_filename = self.baselines.get(self.active_baseline).map(|b| b.filename.clone());
+        self.baselines = load_baseline_items(&self.data_dir);
+        // Merges and deletions rewrite chains under the same file names
+        self.active_files = None;
+        self.active_baseline = active_filename
+            .and_then(|f| self.baselines.iter().position(|b| b.filename == f))
+            .unwrap_or_else(|| default_active_baseline(&self.baselines));
+        self.mark_active_baseline();
+        let baselines = &self.baselines;
+        self.marked_baselines.retain(|f| baselines.iter().any(|b| &b.filename == f));
+        if self.baseline_browser.as_ref().is_some_and(|browser| !baselines.iter().any(|b| b.filename == browser.filename)) {
+            self.baseline_browser = None;
+        }
+        
+        if self.selected_baseline >= self.baselines.len() {
+            self.selected_baseline = self.baselines.len().saturating_sub(1);
+        }
+    }
+    
+    /// Compare the selected baseline with the active one (older to newer),
+    /// in the background. False if nothing was started.
+    pub fn compare_selected_to_active(&mut self) -> bool {
+        let (Some(selected), Some(active)) = (
+            self.baselines.get(self.selected_baseline),
+            self.baselines.get(self.active_baseline),
+        ) else {
+            return false;
+        };
+        
+        let (from, to) = if selected.created_at <= active.created_at {
+            (selected.filename.clone(), active.filename.clone())
+        } else {
+            (active.filename.clone(), selected.filename.clone())
+        };
+        self.start_stored_compare(from, to)
+    }
+    
+    /// Compare two stored baselines in the background; see `poll_stored_compare`.
+    /// False if a comparison is already running.
+    fn start_stored_compare(&mut self, from: String, to: String) -> bool {
+        if self.stored_compare.is_some() {
+            self.toasts.push(Toast::info("Already comparing baselines".to_string()));
+            return false;
+        }
+        self.stored_compare = Some(StoredCompare::start(self.data_dir.clone(), from, to));
+        self.toasts.push(Toast::info("Comparing baselines...".to_string()));
+        true
+    }
+    
+    /// Enter in the Baselines view: start browsing the selected baseline's
+    /// files, or fold the directory / view the file under the cursor
+    pub fn browse_selected_baseline(&mut self) {
+        if let Some(browser) = &mut self.baseline_browser {
+            if !browser.open_selected() {
+                self.toasts.push(Toast::info("Only existence is tracked for this file - no content stored".to_string()));
             }
+            return;
         }
+        let Some(baseline) = self.baselines.get(self.selected_baseline) else {
+            return;
+        };
+        let filename = baseline.filename.clone();
         
-        // Sort baselines: deltas first (newest first), then initial baseline at the end
-        baselines.sort_by(|a, b| {
-            if a.is_initial && !b.is_initial {
-                std::cmp::Ordering::Greater // Initial goes to end
-            } else if !a.is_initial && b.is_initial {
-                std::cmp::Ordering::Less
-            } else if !a.is_initial && !b.is_initial {
-                // Both deltas - sort by version (newest first)
-                b.version.cmp(&a.version)
-            } else {
-                std::cmp::Ordering::Equal
+        let state = BaselineIndex::load_or_rebuild(&self.data_dir)
+            .and_then(|index| crate::compare::materialize(&self.data_dir, &index, &filename));
+        match state {
+            Ok(state) => self.baseline_browser = Some(BaselineFiles::new(filename, state)),
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Cannot Browse Baseline".to_string(),
+                    format!("Could not load {}: {}", filename, e),
+                ));
             }
+        }
+    }
+    
+    /// Esc while browsing: close the viewer, then the file tree
+    pub fn close_baseline_browser(&mut self) {
+        match &mut self.baseline_browser {
+            Some(browser) if browser.viewing.is_some() => browser.viewing = None,
+            _ => self.baseline_browser = None,
+        }
+    }
+    
+    /// Mark or unmark the selected baseline for a side-by-side comparison.
+    /// Marking a third drops the one marked first.
+    pub fn toggle_selected_mark(&mut self) {
+        let Some(baseline) = self.baselines.get(self.selected_baseline) else {
+            return;
+        };
+        if let Some(pos) = self.marked_baselines.iter().position(|f| *f == baseline.filename) {
+            self.marked_baselines.remove(pos);
+            return;
+        }
+        self.marked_baselines.push(baseline.filename.clone());
+        if self.marked_baselines.len() > 2 {
+            self.marked_baselines.remove(0);
+        }
+        if self.marked_baselines.len() == 2 {
+            self.toasts.push(Toast::info("Two baselines marked - press [s] to compare them".to_string()));
+        }
+    }
+    
+    /// The marked baselines, older first
+    pub fn marked_pair(&self) -> Option<(&BaselineItem, &BaselineItem)> {
+        let [a, b] = self.marked_baselines.as_slice() else {
+            return None;
+        };
+        let find = |filename: &String| self.baselines.iter().find(|item| &item.filename == filename);
+        let (a, b) = (find(a)?, find(b)?);
+        Some(if a.created_at <= b.created_at { (a, b) } else { (b, a) })
+    }
+    
+    /// Compare the two marked baselines and show them side by side
+    pub fn compare_marked(&mut self) {
+        let Some((from, to)) = self.marked_pair() else {
+            self.toasts.push(Toast::info("Mark two baselines with [m] first".to_string()));
+            return;
+        };
+        let (from, to) = (from.filename.clone(), to.filename.clone());
+        if self.start_stored_compare(from, to) {
+            if let Some(compare) = &mut self.stored_compare {
+                compare.open_changes = true;
+                compare.split = true;
+            }
+        }
+    }
+    
+    pub fn toggle_split_compare(&mut self) {
+        self.split_compare = !self.split_compare;
+        self.change_detail_scroll = 0;
+    }
+    
+    /// Compare the live system against the active baseline. The scan runs
+    /// in the background; see `process_progress_updates`. A Dashboard
+    /// refresh already scanning against the same baseline is taken over
+    /// rather than scanning twice.
+    pub fn compare_live_to_active(&mut self) {
+        if self.live_compare.is_some() {
+            self.toasts.push(Toast::info("Already scanning the live system".to_string()));
+            return;
+        }
+        let Some(active) = self.baselines.get(self.active_baseline) else {
+            self.toasts.push(Toast::info("No baselines yet - create one in the Baselines tab".to_string()));
+            return;
+        };
+        
+        if self.dashboard_refresh.as_ref().is_some_and(|r| r.filename == active.filename) {
+            self.live_compare = self.dashboard_refresh.take();
+            self.toasts.push(Toast::info(format!("Joining the running scan against {}", active.version)));
+            return;
+        }
+        self.live_compare = Some(LiveCompare::start(
+            self.data_dir.clone(),
+            active.filename.clone(),
+            self.config.baseline.clone(),
+        ));
+        self.toasts.push(Toast::info(format!("Scanning the live system against {}", active.version)));
+    }
+    
+    /// Show `comparison` in the Changes view, keeping the filters. The
+    /// split layout is left off; `poll_stored_compare` turns it back on.
+    fn set_comparison(&mut self, comparison: Comparison) {
+        self.comparison_results = Some(ComparisonResults {
+            changed: comparison.count(ChangeKind::Modified),
+            new: comparison.count(ChangeKind::Added),
+            deleted: comparison.count(ChangeKind::Removed),
+            packages_added: 0,
+            packages_removed: 0,
+            packages_upgraded: 0,
         });
+        self.comparison = Some(comparison);
+        self.comparison_stale = false;
+        self.split_compare = false;
+        self.refresh_packages();
+        self.collapsed_dirs.clear();
+        self.selected_change = 0;
+        self.refresh_change_rows();
+    }
+    
+    /// Re-apply the filters and layout to the comparison. An invalid path
+    /// glob is reported and dropped.
+    pub fn refresh_change_rows(&mut self) {
+        let Some(comparison) = &self.comparison else {
+            self.change_rows.clear();
+            self.selected_change = 0;
+            return;
+        };
         
-        // Set active baseline (first delta, or initial if no deltas)
-        let active_baseline = if baselines.iter().any(|b| !b.is_initial) {
-            baselines.iter().position(|b| !b.is_initial).unwrap_or(0)
+        let indices = match self.change_filter.apply(comparison, &self.packages) {
+            Ok(indices) => indices,
+            Err(e) => {
+                self.toasts.push(Toast::error(format!("Invalid path filter: {}", e)));
+                self.change_filter.path = None;
+                return self.refresh_change_rows();
+            }
+        };
+        self.change_rows = if self.change_tree {
+            crate::browse::tree_rows(comparison, &indices, &self.collapsed_dirs)
         } else {
-            baselines.len().saturating_sub(1)
+            crate::browse::flat_rows(&indices)
+        };
+        self.selected_change = self.selected_change.min(self.change_rows.len().saturating_sub(1));
+        self.change_detail_scroll = 0;
+    }
+    
+    /// The change under the cursor, if the cursor is on a file
+    pub fn selected_file_change(&self) -> Option<&crate::compare::FileChange> {
+        match self.change_rows.get(self.selected_change)? {
+            Row::File { index, .. } => self.comparison.as_ref()?.changes.get(*index),
+            Row::Dir { .. } => None,
+        }
+    }
+    
+    pub fn toggle_change_tree(&mut self) {
+        // Keep the cursor on the same change across layouts
+        let selected = self.change_rows.get(self.selected_change).cloned();
+        self.change_tree = !self.change_tree;
+        self.refresh_change_rows();
+        if let Some(Row::File { index, .. }) = selected {
+            if let Some(pos) = self.change_rows.iter().position(|r| matches!(r, Row::File { index: i, .. } if *i == index)) {
+                self.selected_change = pos;
+            }
+        }
+    }
+    
+    /// Fold or unfold the directory under the cursor
+    pub fn toggle_selected_dir(&mut self) {
+        if let Some(Row::Dir { path, .. }) = self.change_rows.get(self.selected_change) {
+            let path = path.clone();
+            if !self.collapsed_dirs.remove(&path) {
+                self.collapsed_dirs.insert(path);
+            }
+            self.refresh_change_rows();
+        }
+    }
+    
+    pub fn cycle_type_filter(&mut self) {
+        self.change_filter.kind = self.change_filter.kind.next();
+        self.selected_change = 0;
+        self.refresh_change_rows();
+    }
+    
+    pub fn cycle_severity_filter(&mut self) {
+        self.change_filter.severity = crate::browse::next_severity(self.change_filter.severity);
+        self.selected_change = 0;
+        self.refresh_change_rows();
+    }
+    
+    pub fn show_path_filter_input(&mut self) {
+        self.pending_action = Some(PendingAction::FilterPath);
+        self.popup = Some(tui_components::Popup::input(
+            "Filter by Path".to_string(),
+            "Path or glob (e.g. /etc, *.conf, /usr/**/bin/*); empty to clear:".to_string(),
+            self.change_filter.path.clone().unwrap_or_default(),
+        ));
+    }
+    
+    pub fn show_package_filter_input(&mut self) {
+        if self.packages.is_empty() {
+            let message = if self.packages_load.is_some() {
+                "Still reading the package database"
+            } else {
+                "No package database found under the scan path"
+            };
+            self.toasts.push(Toast::info(message.to_string()));
+            return;
+        }
+        self.pending_action = Some(PendingAction::FilterPackage);
+        self.popup = Some(tui_components::Popup::input(
+            "Filter by Package".to_string(),
+            "Owning package; empty to clear:".to_string(),
+            self.change_filter.package.clone().unwrap_or_default(),
+        ));
+    }
+    
+    pub fn clear_change_filters(&mut self) {
+        if self.change_filter.is_active() {
+            self.change_filter = ChangeFilter::default();
+            self.refresh_change_rows();
+            self.toasts.push(Toast::info("Filters cleared".to_string()));
+        }
+    }
+    
+    pub fn scroll_change_detail(&mut self, down: bool) {
+        self.change_detail_scroll = if down {
+            self.change_detail_scroll.saturating_add(5)
+        } else {
+            self.change_detail_scroll.saturating_sub(5)
+        };
+    }
+    
+    /// Select `path` in the Changes view, clearing filters that hide it.
+    /// Returns false when the comparison doesn't include it.
+    fn focus_change(&mut self, path: &str) -> bool {
+        let Some(index) = self.comparison.as_ref().and_then(|c| c.changes.iter().position(|ch| ch.path == path)) else {
+            return false;
+        };
+        let is_row = |rows: &[Row]| rows.iter().position(|r| matches!(r, Row::File { index: i, .. } if *i == index));
+        if is_row(&self.change_rows).is_none() {
+            self.change_filter = ChangeFilter::default();
+            self.collapsed_dirs.clear();
+            self.refresh_change_rows();
+        }
+        if let Some(pos) = is_row(&self.change_rows) {
+            self.selected_change = pos;
+            self.change_detail_scroll = 0;
+        }
+        true
+    }
+    
+    /// Show the diff of `path` in the Changes view, comparing the live
+    /// system first when the current comparison doesn't include it
+    pub fn show_change(&mut self, path: &str) {
+        self.set_view(ViewMode::Changes);
+        if self.focus_change(path) {
+            return;
+        }
+        self.pending_change_focus = Some(path.to_string());
+        self.compare_live_to_active();
+    }
+    
+    /// `/` searches paths, `?` searches stored contents
+    pub fn show_search_input(&mut self, mode: SearchMode) {
+        let (title, prompt) = match mode {
+            SearchMode::Paths => (
+                "Search Paths",
+                "Fuzzy path match in the active baseline and current changes (e.g. bootcfg):",
+            ),
+            SearchMode::Contents => (
+                "Search Contents",
+                "Text in the content stored by any baseline (e.g. dtoverlay=):",
+            ),
+        };
+        let initial = self.search.as_ref().filter(|s| s.mode == mode).map(|s| s.query.clone()).unwrap_or_default();
+        self.pending_action = Some(PendingAction::Search { mode });
+        self.popup = Some(tui_components::Popup::input(title.to_string(), prompt.to_string(), initial));
+    }
+    
+    fn run_search(&mut self, mode: SearchMode, query: &str) {
+        let results = match mode {
+            // Loads every baseline, so it runs in the background; see
+            // `poll_content_search`
+            SearchMode::Contents => {
+                if let Some(search) = self.content_search.take() {
+                    search.stop();
+                }
+                self.content_search = Some(ContentSearch::start(self.data_dir.clone(), query.to_string()));
+                self.toasts.push(Toast::info(format!("Searching stored contents for \"{}\"", query)));
+                return;
+            }
+            SearchMode::Paths => match self.load_active_files() {
+                Ok(()) => {
+                    let active = self.baselines.get(self.active_baseline)
+                        .zip(self.active_files.as_ref())
+                        .map(|(b, (_, files))| (b.filename.as_str(), b.version.as_str(), files));
+                    Ok(crate::search::search_paths(query, active, self.comparison.as_ref()))
+                }
+                Err(e) => Err(e),
+            },
+        };
+        self.show_search_results(mode, query, results);
+    }
+    
+    /// Materialize the active baseline into `active_files` unless it's
+    /// already there
+    fn load_active_files(&mut self) -> std::io::Result<()> {
+        let Some(active) = self.baselines.get(self.active_baseline) else {
+            self.active_files = None;
+            return Ok(());
+        };
+        if self.active_files.as_ref().is_some_and(|(filename, _)| *filename == active.filename) {
+            return Ok(());
+        }
+        let index = BaselineIndex::load_or_rebuild(&self.data_dir)?;
+        let files = crate::compare::materialize(&self.data_dir, &index, &active.filename)?;
+        self.active_files = Some((active.filename.clone(), files));
+        Ok(())
+    }
+    
+    /// Show the content search's results once it has finished
+    pub fn poll_content_search(&mut self) {
+        let Some(search) = self.content_search.take() else {
+            return;
+        };
+        let query = search.query.clone();
+        match search.finish() {
+            Ok(results) => self.show_search_results(SearchMode::Contents, &query, results),
+            Err(search) => self.content_search = Some(search),
+        }
+    }
+    
+    fn show_search_results(&mut self, mode: SearchMode, query: &str, results: std::io::Result<SearchResults>) {
+        match results {
+            Ok(results) if results.hits.is_empty() => {
+                let message = match mode {
+                    SearchMode::Paths => format!("No paths match \"{}\"", query),
+                    SearchMode::Contents => format!("No stored content contains \"{}\"", query),
+                };
+                self.toasts.push(Toast::info(message));
+            }
+            Ok(results) => self.search = Some(results),
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Search Failed".to_string(),
+                    format!("Could not search baselines: {}", e),
+                ));
+            }
+        }
+    }
+    
+    /// Jump to the selected search result: a change opens in the Changes
+    /// view, a baseline file in the Baselines file browser
+    pub fn open_search_hit(&mut self) {
+        let Some(hit) = self.search.as_ref().and_then(|s| s.selected_hit()).cloned() else {
+            return;
+        };
+        self.search = None;
+        match hit.source {
+            HitSource::Change => self.show_change(&hit.path),
+            HitSource::Baseline { filename, .. } => {
+                let line = hit.line.map(|(number, _)| number);
+                self.reveal_in_baseline(&filename, &hit.path, line);
+            }
+        }
+    }
+    
+    /// Show `path` as recorded in `filename` in the Baselines file browser
+    fn reveal_in_baseline(&mut self, filename: &str, path: &str, line: Option<usize>) {
+        let Some(position) = self.baselines.iter().position(|b| b.filename == filename) else {
+            self.toasts.push(Toast::info("That baseline no longer exists".to_string()));
+            return;
         };
+        self.set_view(ViewMode::Baselines);
+        if self.baseline_browser.as_ref().is_none_or(|browser| browser.filename != filename) {
+            self.baseline_browser = None;
+            self.selected_baseline = position;
+            self.browse_selected_baseline();
+        }
+        if let Some(browser) = &mut self.baseline_browser {
+            if !browser.reveal(path, line) {
+                self.toasts.push(Toast::info(format!("{} is not in this baseline", path)));
+            }
+        }
+    }
+    
+    /// The file the cursor is on in the current view or search results
+    fn context_path(&self) -> Option<String> {
+        if let Some(search) = &self.search {
+            return search.selected_hit().map(|hit| hit.path.clone());
+        }
+        match self.current_view {
+            ViewMode::Changes => self.selected_file_change().map(|change| change.path.clone()),
+            ViewMode::Baselines => {
+                let browser = self.baseline_browser.as_ref()?;
+                match browser.viewing {
+                    Some(index) => browser.files.get(index).map(|entry| entry.path.clone()),
+                    None => browser.selected_file().map(|entry| entry.path.clone()),
+                }
+            }
+            ViewMode::Timeline => match &self.timeline.get(self.selected_event)?.kind {
+                crate::timeline::EventKind::File { path, .. } => Some(path.clone()),
+                _ => None,
+            },
+            ViewMode::Dashboard => None,
+        }
+    }
+    
+    /// History of the file under the cursor, or of a path asked for when
+    /// the cursor isn't on a file
+    pub fn show_history(&mut self) {
+        if let Some(path) = self.context_path() {
+            self.open_history(&path);
+            return;
+        }
+        self.pending_action = Some(PendingAction::History);
+        self.popup = Some(tui_components::Popup::input(
+            "File History".to_string(),
+            "Path to trace across the baselines (e.g. /boot/firmware/config.txt):".to_string(),
+            String::new(),
+        ));
+    }
+    
+    fn open_history(&mut self, path: &str) {
+        let root = Path::new(&self.config.baseline.scan_path);
+        let events = crate::packages::read_dpkg_log(root);
+        let _ = self.journal.sync(&self.data_dir);
         
-        // Mark active baseline
-        if let Some(b) = baselines.get_mut(active_baseline) {
-            b.is_active = true;
+        match crate::history::file_history(&self.data_dir, path, self.packages.owner(path), &events, self.journal.entries()) {
+            Ok(history) if history.steps.is_empty() => {
+                self.toasts.push(Toast::info(format!("No baseline records {}", path)));
+            }
+            Ok(history) => {
+                self.search = None;
+                self.history = Some(history);
+            }
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "History Failed".to_string(),
+                    format!("Could not read the baselines: {}", e),
+                ));
+            }
+        }
+    }
+    
+    /// Show the file as recorded at the selected history step
+    pub fn open_history_step(&mut self) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        let Some(step) = history.selected_step() else {
+            return;
+        };
+        if step.change.kind == ChangeKind::Removed {
+            self.toasts.push(Toast::info("The file was removed in this baseline".to_string()));
+            return;
+        }
+        let (filename, path) = (step.filename.clone(), history.path.clone());
+        self.history = None;
+        self.reveal_in_baseline(&filename, &path, None);
+    }
+    
+    /// Reload the Timeline's sources: baselines, the change journal and the
+    /// package log of the scanned system
+    pub fn refresh_timeline(&mut self) {
+        if let Err(e) = self.journal.sync(&self.data_dir) {
+            self.toasts.push(Toast::error(format!("Could not read the change journal: {}", e)));
+        }
+        let packages = crate::packages::read_dpkg_log(Path::new(&self.config.baseline.scan_path));
+        self.timeline = crate::timeline::build(&self.baselines, self.journal.entries(), &packages);
+        self.selected_event = self.selected_event.min(self.timeline.len().saturating_sub(1));
+    }
+    
+    pub fn move_event_up(&mut self) {
+        self.selected_event = self.selected_event.saturating_sub(1);
+    }
+    
+    pub fn move_event_down(&mut self) {
+        if self.selected_event + 1 < self.timeline.len() {
+            self.selected_event += 1;
+        }
+    }
+    
+    /// Jump from the selected Timeline entry: a baseline is compared with
+    /// the active one, a file change opens its diff and a package event
+    /// filters the Changes view to that package
+    pub fn open_selected_event(&mut self) {
+        let Some(event) = self.timeline.get(self.selected_event).cloned() else {
+            return;
+        };
+        match event.kind {
+            crate::timeline::EventKind::Baseline { filename, .. } => self.compare_with_active(&filename),
+            crate::timeline::EventKind::File { path, .. } => self.show_change(&path),
+            crate::timeline::EventKind::Package { package, .. } => {
+                if self.comparison.is_none() {
+                    self.toasts.push(Toast::info("Nothing to filter - compare first".to_string()));
+                    return;
+                }
+                self.change_filter.package = Some(package);
+                self.selected_change = 0;
+                self.refresh_change_rows();
+                self.set_view(ViewMode::Changes);
+            }
+        }
+    }
+    
+    /// Compare a baseline with the active one and show the result
+    pub fn compare_with_active(&mut self, filename: &str) {
+        let Some(position) = self.baselines.iter().position(|b| b.filename == filename) else {
+            self.toasts.push(Toast::info("That baseline no longer exists".to_string()));
+            return;
+        };
+        if position == self.active_baseline {
+            self.toasts.push(Toast::info("That is the active baseline".to_string()));
+            return;
+        }
+        self.selected_baseline = position;
+        if self.compare_selected_to_active() {
+            if let Some(compare) = &mut self.stored_compare {
+                compare.open_changes = true;
+            }
         }
+    }
+    
+    /// Use the selected baseline as the comparison target
+    pub fn set_selected_active(&mut self) {
+        if self.selected_baseline >= self.baselines.len() || self.selected_baseline == self.active_baseline {
+            return;
+        }
+        self.active_baseline = self.selected_baseline;
+        self.mark_active_baseline();
+        // Results were computed against the previous target
+        self.comparison_results = None;
+        self.comparison = None;
+        self.comparison_stale = false;
+        self.refresh_change_rows();
         
-        Self {
-            config,
-            data_dir,
-            current_view: ViewMode::Baselines,
-            should_quit: false,
-            baselines,
-            selected_baseline: 0,
-            active_baseline,
-            comparison_results: None,
-            popup: None,
-            creating_baseline: false,
-            creating_initial: false,
-            baseline_progress: Vec::new(),
-            baseline_completed: Vec::new(),
-            baseline_files_processed: 0,
-            baseline_estimated_total: None,
-            baseline_start_time: None,
-            package_db_progress: None,
-            baseline_phase: "scanning".to_string(),
-            progress_rx: None,
+        if let Err(e) = self.save_state() {
+            self.popup = Some(tui_components::Popup::error(
+                "State Not Saved".to_string(),
+                format!("Could not save {}: {}", AppState::path(&self.data_dir).display(), e),
+            ));
+        }
+    }
+    
+    /// Persist the active baseline, selection and view for the next launch
+    pub fn save_state(&self) -> std::io::Result<()> {
+        let filename_at = |idx: usize| self.baselines.get(idx).map(|b| b.filename.clone());
+        AppState {
+            active_baseline: filename_at(self.active_baseline),
+            selected_baseline: filename_at(self.selected_baseline),
+            last_view: Some(self.current_view),
+        }
+        .save(&self.data_dir)
+    }
+    
+    fn mark_active_baseline(&mut self) {
+        for (idx, baseline) in self.baselines.iter_mut().enumerate() {
+            baseline.is_active = idx == self.active_baseline;
         }
     }
     
     pub fn show_delete_confirmation(&mut self) {
-        if let Some(baseline) = self.baselines.get(self.selected_baseline) {
-            self.popup = Some(tui_components::Popup::confirm(
-                "Delete Baseline".to_string(),
-                format!("Delete baseline: {}?", baseline.version),
+        let Some(baseline) = self.baselines.get(self.selected_baseline) else {
+            return;
+        };
+        if baseline.pinned {
+            self.popup = Some(tui_components::Popup::error(
+                "Baseline Protected".to_string(),
+                format!("{} is pinned. Unpin it with [p] before deleting.", baseline.version),
             ));
+            return;
+        }
+        
+        let index = match BaselineIndex::load_or_rebuild(&self.data_dir) {
+            Ok(index) => index,
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Delete Failed".to_string(),
+                    format!("Could not read baselines: {}", e),
+                ));
+                return;
+            }
+        };
+        
+        let filename = baseline.filename.clone();
+        match index.delete_impact(&filename) {
+            DeleteImpact::ChainRoot { deltas } => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Baseline In Use".to_string(),
+                    format!(
+                        "{} deltas are built on the initial baseline. Delete or prune them first.",
+                        deltas,
+                    ),
+                ));
+            }
+            DeleteImpact::HasSuccessor { version, .. } => {
+                self.popup = Some(tui_components::Popup::confirm(
+                    "Delete Baseline".to_string(),
+                    format!(
+                        "{} is the parent of {}. Merge its changes into {} and delete it?",
+                        baseline.version, version, version,
+                    ),
+                ));
+                self.pending_action = Some(PendingAction::MergeDeleteBaseline { filename });
+            }
+            DeleteImpact::Standalone => {
+                self.popup = Some(tui_components::Popup::confirm(
+                    "Delete Baseline".to_string(),
+                    format!("Delete baseline: {}?", baseline.version),
+                ));
+                self.pending_action = Some(PendingAction::DeleteBaseline { filename });
+            }
         }
     }
     
@@ -203,6 +1201,7 @@ impl App {
                         *selected -= 1;
                     }
                 }
+                tui_components::PopupType::Input { .. } => popup.move_cursor_left(),
                 _ => {}
             }
         }
@@ -216,21 +1215,58 @@ impl App {
                         *selected += 1;
                     }
                 }
+                tui_components::PopupType::Input { .. } => popup.move_cursor_right(),
                 _ => {}
             }
         }
     }
     
     pub fn popup_confirm(&mut self) {
+        let pending = self.pending_action.take();
         if let Some(popup) = self.popup.take() {
             match popup.popup_type {
-                tui_components::PopupType::Confirm { message, selected, .. } => {
-                    if selected == 0 {
-                        // Yes was selected - extract version from message
-                        // Message format: "Delete baseline: {version}?"
-                        if let Some(version) = message.strip_prefix("Delete baseline: ").and_then(|s| s.strip_suffix("?")) {
-                            self.delete_baseline(version);
+                tui_components::PopupType::Input { input, .. } => {
+                    match pending {
+                        Some(PendingAction::EditLabel { filename }) => {
+                            self.update_baseline_metadata(&filename, |b| b.label = non_empty(&input));
+                        }
+                        Some(PendingAction::EditNotes { filename }) => {
+                            self.update_baseline_metadata(&filename, |b| b.notes = non_empty(&input));
+                        }
+                        Some(PendingAction::FilterPath) => {
+                            self.change_filter.path = non_empty(&input);
+                            self.selected_change = 0;
+                            self.refresh_change_rows();
+                        }
+                        Some(PendingAction::FilterPackage) => {
+                            self.change_filter.package = non_empty(&input);
+                            self.selected_change = 0;
+                            self.refresh_change_rows();
+                        }
+                        Some(PendingAction::Search { mode }) => {
+                            if let Some(query) = non_empty(&input) {
+                                self.run_search(mode, &query);
+                            }
                         }
+                        Some(PendingAction::History) => {
+                            if let Some(path) = non_empty(&input) {
+                                self.open_history(&path);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                tui_components::PopupType::Confirm { selected: 0, .. } => {
+                    match pending {
+                        Some(PendingAction::Prune) => self.prune_baselines(),
+                        Some(PendingAction::DeleteBaseline { filename }) => {
+                            self.delete_baseline(&filename, false);
+                        }
+                        Some(PendingAction::MergeDeleteBaseline { filename }) => {
+                            self.delete_baseline(&filename, true);
+                        }
+                        Some(PendingAction::CancelScan) => self.cancel_scan(),
+                        _ => {}
                     }
                 }
                 _ => {}
@@ -240,18 +1276,127 @@ impl App {
     
     pub fn popup_cancel(&mut self) {
         self.popup = None;
+        self.pending_action = None;
     }
     
-    fn delete_baseline(&mut self, version: &str) {
-        // Remove from list
-        self.baselines.retain(|b| b.version != version);
-        
-        // Adjust selection if needed
-        if self.selected_baseline >= self.baselines.len() && !self.baselines.is_empty() {
-            self.selected_baseline = self.baselines.len() - 1;
+    /// Whether the open popup is a text input (keys go to the input field)
+    pub fn popup_is_input(&self) -> bool {
+        matches!(
+            self.popup.as_ref().map(|p| &p.popup_type),
+            Some(tui_components::PopupType::Input { .. })
+        )
+    }
+    
+    pub fn show_label_input(&mut self) {
+        if let Some(baseline) = self.baselines.get(self.selected_baseline) {
+            self.pending_action = Some(PendingAction::EditLabel {
+                filename: baseline.filename.clone(),
+            });
+            self.popup = Some(tui_components::Popup::input(
+                "Baseline Label".to_string(),
+                format!("Label for {}:", baseline.version),
+                baseline.label.clone().unwrap_or_default(),
+            ));
+        }
+    }
+    
+    pub fn show_notes_input(&mut self) {
+        if let Some(baseline) = self.baselines.get(self.selected_baseline) {
+            self.pending_action = Some(PendingAction::EditNotes {
+                filename: baseline.filename.clone(),
+            });
+            self.popup = Some(tui_components::Popup::input(
+                "Baseline Notes".to_string(),
+                format!("Notes for {}:", baseline.version),
+                baseline.notes.clone().unwrap_or_default(),
+            ));
         }
+    }
+    
+    pub fn toggle_selected_pin(&mut self) {
+        if let Some(baseline) = self.baselines.get(self.selected_baseline) {
+            let filename = baseline.filename.clone();
+            let pinned = !baseline.pinned;
+            self.update_baseline_metadata(&filename, |b| b.pinned = pinned);
+        }
+    }
+    
+    /// Apply a metadata change to a stored baseline and refresh the list
+    fn update_baseline_metadata<F>(&mut self, filename: &str, update: F)
+    where
+        F: FnOnce(&mut crate::baseline::Baseline),
+    {
+        match crate::baseline::Baseline::update_metadata(&self.data_dir, filename, update) {
+            Ok(_) => self.reload_baselines(),
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Update Failed".to_string(),
+                    format!("Could not update {}: {}", filename, e),
+                ));
+            }
+        }
+    }
+    
+    /// Delete a baseline file, first merging it into its successor when a
+    /// later delta depends on it
+    fn delete_baseline(&mut self, filename: &str, merge: bool) {
+        let result = if merge {
+            crate::baseline::Baseline::merge_into_successor(&self.data_dir, filename).map(|_| ())
+        } else {
+            crate::baseline::Baseline::remove_file(&self.data_dir, filename)
+        };
         
-        // TODO: Actually delete the baseline file
+        self.reload_baselines();
+        if let Err(e) = result {
+            self.popup = Some(tui_components::Popup::error(
+                "Delete Failed".to_string(),
+                format!("Could not delete {}: {}", filename, e),
+            ));
+        }
+    }
+    
+    /// Ask before applying the retention policy, showing what it would prune
+    pub fn show_prune_confirmation(&mut self) {
+        match crate::retention::prune(&self.data_dir, &self.config.retention, true) {
+            Ok(plan) if plan.prune.is_empty() => {
+                self.popup = Some(tui_components::Popup::info(
+                    "Nothing to Prune".to_string(),
+                    format!("All {} deltas are kept by the retention policy.", plan.keep.len()),
+                ));
+            }
+            Ok(plan) => {
+                self.pending_action = Some(PendingAction::Prune);
+                self.popup = Some(tui_components::Popup::confirm(
+                    "Prune Baselines".to_string(),
+                    format!(
+                        "Prune {} of {} deltas? Each is squashed into the delta after it.",
+                        plan.prune.len(),
+                        plan.prune.len() + plan.keep.len(),
+                    ),
+                ));
+            }
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Prune Failed".to_string(),
+                    format!("Could not read baselines: {}", e),
+                ));
+            }
+        }
+    }
+    
+    fn prune_baselines(&mut self) {
+        let result = crate::retention::prune(&self.data_dir, &self.config.retention, false);
+        self.reload_baselines();
+        self.popup = Some(match result {
+            Ok(plan) => tui_components::Popup::info(
+                "Baselines Pruned".to_string(),
+                format!("Pruned {} deltas, kept {}.", plan.prune.len(), plan.keep.len()),
+            ),
+            Err(e) => tui_components::Popup::error(
+                "Prune Failed".to_string(),
+                format!("Pruning stopped: {}", e),
+            ),
+        });
     }
     
     pub fn create_initial_baseline(&mut self) {
@@ -265,13 +1410,36 @@ impl App {
             return;
         }
         
-        // Default scan paths (from config.yaml or defaults)
-        let scan_path = "/"; // Default to root
-        let remap_to = "/";
+        self.start_baseline_scan(false);
+    }
+    
+    /// Scan and save a delta against the newest baseline
+    pub fn create_delta_baseline(&mut self) {
+        if !self.baselines.iter().any(|b| b.is_initial) {
+            self.popup = Some(tui_components::Popup::error(
+                "No Initial Baseline".to_string(),
+                "Create the initial baseline with [i] before taking deltas.".to_string(),
+            ));
+            return;
+        }
+        
+        self.start_baseline_scan(true);
+    }
+    
+    /// Run the scan for a new baseline on a background thread, reporting
+    /// progress through `progress_rx`
+    fn start_baseline_scan(&mut self, delta: bool) {
+        if self.creating_baseline {
+            return;
+        }
+        
+        // Scan paths from config.yaml (both default to "/")
+        let scan_path = self.config.baseline.scan_path.clone();
+        let remap_to = self.config.baseline.remap_to.clone();
         
         // Initialize progress state
         self.creating_baseline = true;
-        self.creating_initial = true;
+        self.creating_initial = !delta;
         self.baseline_progress.clear();
         self.baseline_completed.clear();
         self.baseline_files_processed = 0;
@@ -279,22 +1447,23 @@ impl App {
         self.baseline_start_time = Some(SystemTime::now());
         self.package_db_progress = None;
         self.baseline_phase = "scanning".to_string();
+        self.baseline_paused_at = None;
+        self.baseline_paused_for = Duration::ZERO;
         
         // Create channel for progress updates
         let (tx, rx) = mpsc::channel();
         self.progress_rx = Some(rx);
         
-        // Create cancel flag
-        let cancel_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        // Kept so the scan can be paused or cancelled from the UI
+        let control = ScanControl::new();
+        self.scan_control = Some(control.clone());
         
         // Clone data needed for background thread
         let data_dir = self.data_dir.clone();
         let config = self.config.baseline.clone();
-        let scan_path = scan_path.to_string();
-        let remap_to = remap_to.to_string();
         
         // Spawn baseline creation in background thread
-        std::thread::spawn(move || {
+        self.scan_thread = Some(std::thread::spawn(move || {
             // Create progress callback that sends updates through channel
             let tx_clone = tx.clone();
             let progress_callback = move |phase: &str, current: usize, status: &str| {
@@ -342,17 +1511,42 @@ impl App {
             };
             
             // Create the baseline
-            let result = crate::baseline::create_initial_baseline(
-                &scan_path,
-                &remap_to,
-                &data_dir,
-                &config,
-                cancel_flag,
-                progress_callback,
-            );
+            let result = if delta {
+                crate::baseline::create_delta_baseline(
+                    &scan_path,
+                    &remap_to,
+                    &data_dir,
+                    &config,
+                    control.clone(),
+                    progress_callback,
+                )
+            } else {
+                crate::baseline::create_initial_baseline(
+                    &scan_path,
+                    &remap_to,
+                    &data_dir,
+                    &config,
+                    control.clone(),
+                    progress_callback,
+                )
+            };
             
             // Send completion message
             match result {
+                Err(_) if control.is_cancelled() => {
+                    let _ = tx.send(ProgressUpdate::BaselineCancelled);
+                }
+                Ok(baseline) if baseline.is_delta => {
+                    let _ = tx.send(ProgressUpdate::BaselineCompleted {
+                        success: true,
+                        message: format!(
+                            "Delta {} created: {} changed, {} removed",
+                            baseline.version,
+                            baseline.file_count,
+                            baseline.removed.len(),
+                        ),
+                    });
+                }
                 Ok(baseline) => {
                     let _ = tx.send(ProgressUpdate::BaselineCompleted {
                         success: true,
@@ -366,7 +1560,374 @@ impl App {
                     });
                 }
             }
+        }));
+    }
+    
+    /// Ask before stopping the running scan
+    pub fn request_cancel_scan(&mut self) {
+        if !self.creating_baseline {
+            return;
+        }
+        self.popup = Some(tui_components::Popup::confirm(
+            "Cancel Baseline Creation".to_string(),
+            "Stop the scan? Files scanned so far are discarded.".to_string(),
+        ));
+        self.pending_action = Some(PendingAction::CancelScan);
+    }
+    
+    /// Stop the running scan. The worker reports back once it has wound
+    /// down; see `ProgressUpdate::BaselineCancelled`.
+    pub fn cancel_scan(&mut self) {
+        if let Some(control) = &self.scan_control {
+            control.cancel();
+            self.baseline_phase = "cancelling".to_string();
+        }
+    }
+    
+    pub fn toggle_scan_pause(&mut self) {
+        let Some(control) = &self.scan_control else {
+            return;
+        };
+        if control.is_cancelled() {
+            return;
+        }
+        
+        let paused = !control.is_paused();
+        control.set_paused(paused);
+        if paused {
+            self.baseline_paused_at = Some(SystemTime::now());
+            self.toasts.push(Toast::info("Scan paused".to_string()));
+        } else {
+            if let Some(paused_at) = self.baseline_paused_at.take() {
+                self.baseline_paused_for += paused_at.elapsed().unwrap_or_default();
+            }
+            self.toasts.push(Toast::info("Scan resumed".to_string()));
+        }
+    }
+    
+    pub fn scan_paused(&self) -> bool {
+        self.scan_control.as_ref().is_some_and(|c| c.is_paused())
+    }
+    
+    /// Time the running scan has spent working, pauses excluded
+    pub fn baseline_elapsed(&self) -> Duration {
+        let Some(start_time) = self.baseline_start_time else {
+            return Duration::ZERO;
+        };
+        let until = self.baseline_paused_at.unwrap_or_else(SystemTime::now);
+        until.duration_since(start_time).unwrap_or_default().saturating_sub(self.baseline_paused_for)
+    }
+    
+    /// Cancel any running scan and wait for its thread, so quitting never
+    /// leaves a worker behind writing to the data dir
+    pub fn shutdown(&mut self) {
+        if let Some(control) = self.scan_control.take() {
+            control.cancel();
+        }
+        if let Some(handle) = self.scan_thread.take() {
+            let _ = handle.join();
+        }
+        if let Some(live) = self.live_compare.take() {
+            live.stop();
+        }
+        if let Some(compare) = self.stored_compare.take() {
+            compare.stop();
+        }
+        if let Some(refresh) = self.dashboard_refresh.take() {
+            refresh.stop();
+        }
+        if let Some(search) = self.content_search.take() {
+            search.stop();
+        }
+    }
+    
+    /// Verify the active baseline's chain on a background thread, since it
+    /// means loading every baseline in it with its stored contents. Other
+    /// chains are left to `chamon baseline verify`; comparisons and the
+    /// Dashboard build on this one.
+    fn start_integrity_check(&mut self) {
+        let Some(active) = self.baselines.get(self.active_baseline) else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        let data_dir = self.data_dir.clone();
+        let filename = active.filename.clone();
+        std::thread::spawn(move || {
+            let tampered = crate::integrity::verify_chain(&data_dir, &filename)
+                .unwrap_or_default()
+                .iter()
+                .filter(|issue| issue.problem.is_tampering())
+                .map(|issue| issue.to_string())
+                .collect();
+            let _ = tx.send(tampered);
+        });
+        self.integrity_check = Some(rx);
+    }
+    
+    /// Re-read the package database in the background when it changed since
+    /// it was last read (a package was installed, or the scan path moved)
+    fn refresh_packages(&mut self) {
+        if self.packages_load.is_some() {
+            return;
+        }
+        let root = PathBuf::from(&self.config.baseline.scan_path);
+        let modified = PackageDb::modified(&root);
+        if self.packages_source.as_ref() == Some(&(root.clone(), modified)) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let packages = PackageDb::load(&root);
+            let _ = tx.send((root, modified, packages));
         });
+        self.packages_load = Some(rx);
+    }
+    
+    /// Take the package database once it has been read, re-applying the
+    /// package filter if one is set
+    pub fn poll_packages(&mut self) {
+        let Some(rx) = &self.packages_load else {
+            return;
+        };
+        let (root, modified, packages) = match rx.try_recv() {
+            Ok(loaded) => loaded,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.packages_load = None;
+                return;
+            }
+        };
+        self.packages_load = None;
+        self.packages = packages;
+        self.packages_source = Some((root, modified));
+        if self.change_filter.package.is_some() {
+            self.refresh_change_rows();
+        }
+    }
+    
+    /// Report tampered baselines once the launch check has finished. The
+    /// result waits while another popup is open rather than replacing it.
+    pub fn poll_integrity_check(&mut self) {
+        if self.popup.is_some() {
+            return;
+        }
+        let Some(rx) = &self.integrity_check else {
+            return;
+        };
+        let tampered = match rx.try_recv() {
+            Ok(tampered) => tampered,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Vec::new(),
+        };
+        self.integrity_check = None;
+        
+        if !tampered.is_empty() {
+            self.popup = Some(tui_components::Popup::error(
+                "Baseline Integrity Check Failed".to_string(),
+                format!(
+                    "{}\n\nThese baselines changed after they were taken. Run `chamon baseline verify` for details.",
+                    tampered.join("\n"),
+                ),
+            ));
+        }
+    }
+    
+    /// Show the live comparison once its scan has finished
+    pub fn poll_live_compare(&mut self) {
+        let Some(live) = self.live_compare.take() else {
+            return;
+        };
+        let started_at = live.started_at;
+        let filename = live.filename.clone();
+        let result = match live.finish() {
+            Ok(result) => result,
+            Err(live) => {
+                self.live_compare = Some(live);
+                return;
+            }
+        };
+        
+        match result {
+            Ok(comparison) => {
+                let count = comparison.changes.len();
+                // The same scan a Dashboard refresh would make
+                if self.baselines.get(self.active_baseline).is_some_and(|a| a.filename == filename) {
+                    self.record_drift(&comparison, &filename, started_at);
+                }
+                self.set_comparison(comparison);
+                self.toasts.push(Toast::success(format!("Live scan done: {} changes", count)));
+                if let Some(path) = self.pending_change_focus.take() {
+                    if !self.focus_change(&path) {
+                        self.toasts.push(Toast::info(format!("{} no longer differs from the baseline", path)));
+                    }
+                }
+            }
+            Err(e) => {
+                self.pending_change_focus = None;
+                self.popup = Some(tui_components::Popup::error(
+                    "Compare Failed".to_string(),
+                    format!("Could not compare the live system: {}", e),
+                ));
+            }
+        }
+    }
+    
+    /// Show a comparison of stored baselines once it has finished, opening
+    /// the Changes view (side by side for marked baselines) if asked to
+    pub fn poll_stored_compare(&mut self) {
+        let Some(compare) = self.stored_compare.take() else {
+            return;
+        };
+        let (open_changes, split) = (compare.open_changes, compare.split);
+        match compare.finish() {
+            Ok(Ok(comparison)) => {
+                self.set_comparison(comparison);
+                self.split_compare = split;
+                if open_changes {
+                    self.set_view(ViewMode::Changes);
+                }
+            }
+            Ok(Err(e)) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Compare Failed".to_string(),
+                    format!("Could not compare baselines: {}", e),
+                ));
+            }
+            Err(compare) => self.stored_compare = Some(compare),
+        }
+    }
+    
+    /// Start a Dashboard refresh: a live scan against the active baseline.
+    /// A running live compare already is one, so none is started beside it.
+    pub fn refresh_dashboard(&mut self) {
+        if self.dashboard_refresh.is_some() || self.live_compare.is_some() {
+            return;
+        }
+        let Some(active) = self.baselines.get(self.active_baseline) else {
+            self.dashboard_error = Some("No baselines yet".to_string());
+            return;
+        };
+        self.dashboard_error = None;
+        self.dashboard_refresh = Some(LiveCompare::start(
+            self.data_dir.clone(),
+            active.filename.clone(),
+            self.config.baseline.clone(),
+        ));
+    }
+    
+    /// Collect a finished Dashboard refresh and start the next one when it
+    /// is due. Scheduled refreshes wait while a baseline is being created or
+    /// a live compare is running.
+    pub fn poll_dashboard_refresh(&mut self) {
+        if let Some(refresh) = self.dashboard_refresh.take() {
+            let started_at = refresh.started_at;
+            let filename = refresh.filename.clone();
+            match refresh.finish() {
+                Ok(Ok(comparison)) => self.record_drift(&comparison, &filename, started_at),
+                Ok(Err(e)) => {
+                    self.dashboard_error = Some(e.to_string());
+                    self.schedule_dashboard_refresh();
+                }
+                Err(refresh) => {
+                    self.dashboard_refresh = Some(refresh);
+                    return;
+                }
+            }
+        }
+        
+        let due = self.next_dashboard_refresh
+            .is_some_and(|at| SystemTime::now() >= at);
+        if due && !self.creating_baseline && self.live_compare.is_none() && !self.baselines.is_empty() {
+            self.next_dashboard_refresh = None;
+            self.refresh_dashboard();
+        }
+    }
+    
+    /// Update the Dashboard and the journal from a finished live scan
+    /// against `filename`, and schedule the next refresh from now
+    fn record_drift(&mut self, comparison: &Comparison, filename: &str, started_at: SystemTime) {
+        let duration = started_at.elapsed().unwrap_or_default();
+        self.drift_summary = Some(DriftSummary::new(comparison, filename, SystemTime::now(), duration));
+        let keep_days = self.config.retention.keep_journal_days;
+        self.dashboard_error = self.journal.record(&self.data_dir, comparison, keep_days)
+            .err()
+            .map(|e| format!("could not update the journal: {}", e));
+        self.schedule_dashboard_refresh();
+    }
+    
+    fn schedule_dashboard_refresh(&mut self) {
+        let minutes = self.config.dashboard.refresh_minutes;
+        self.next_dashboard_refresh = (minutes > 0)
+            .then(|| SystemTime::now() + Duration::from_secs(minutes * 60));
+    }
+    
+    /// Forget the finished scan's switches and reap its thread
+    fn finish_scan(&mut self) {
+        self.creating_baseline = false;
+        self.creating_initial = false;
+        self.scan_control = None;
+        self.baseline_paused_at = None;
+        if let Some(handle) = self.scan_thread.take() {
+            let _ = handle.join();
+        }
+    }
+    
+    /// Reload the config if the watcher saw a change
+    pub fn poll_config_changes(&mut self) {
+        let changed = self.config_watcher.as_mut().is_some_and(|w| w.poll());
+        if changed {
+            self.reload_config();
+        }
+    }
+    
+    /// Re-read the config files. A config with errors is rejected and the
+    /// running settings are kept.
+    pub fn reload_config(&mut self) {
+        let loaded = Config::load(self.config_path.as_deref());
+        let diagnostics = crate::validate::validate(&loaded);
+        
+        if crate::validate::has_errors(&diagnostics) {
+            let details: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+            if self.popup.is_none() {
+                self.popup = Some(tui_components::Popup::error(
+                    "Config Reload Failed".to_string(),
+                    format!("Keeping the previous settings.\n{}", details.join("\n")),
+                ));
+            } else {
+                // Don't throw away whatever the open popup is doing
+                self.toasts.push(Toast::error("Config reload failed - run `chamon config check`".to_string()));
+            }
+            return;
+        }
+        
+        let rules_changed = loaded.config.baseline != self.config.baseline;
+        let refresh_changed = loaded.config.dashboard.refresh_minutes != self.config.dashboard.refresh_minutes;
+        let new_data_dir = loaded.config.data_dir();
+        self.config = loaded.config;
+        
+        if refresh_changed && self.dashboard_refresh.is_none() {
+            self.schedule_dashboard_refresh();
+        }
+        
+        if rules_changed && self.comparison_results.is_some() {
+            self.comparison_stale = true;
+        }
+        if new_data_dir != self.data_dir {
+            self.data_dir = new_data_dir;
+            self.journal = Journal::default();
+            self.reload_baselines();
+        }
+        
+        let message = match diagnostics.len() {
+            0 => "Config reloaded".to_string(),
+            n => format!("Config reloaded with {} warnings", n),
+        };
+        self.toasts.push(Toast::success(message));
+    }
+    
+    /// Drop toasts that have been shown long enough
+    pub fn expire_toasts(&mut self) {
+        self.toasts.retain(|t| t.shown_at.elapsed().map(|e| e < TOAST_DURATION).unwrap_or(false));
     }
     
     /// Process pending progress updates from the background thread
@@ -409,13 +1970,11 @@ impl App {
                     }
                     ProgressUpdate::BaselineCompleted { success, message } => {
                         // Reset progress state
-                        self.creating_baseline = false;
-                        self.creating_initial = false;
+                        self.finish_scan();
                         should_keep_rx = false; // Don't keep receiver after completion
                         
                         if success {
-                            // Reload baselines list (or add the new one)
-                            // For now, just show success popup
+                            self.reload_baselines();
                             self.popup = Some(tui_components::Popup::info(
                                 "Baseline Created".to_string(),
                                 message,
@@ -427,6 +1986,15 @@ impl App {
                             ));
                         }
                     }
+                    ProgressUpdate::BaselineCancelled => {
+                        self.finish_scan();
+                        should_keep_rx = false;
+                        // A confirm popup still open for this scan no longer applies
+                        if self.pending_action == Some(PendingAction::CancelScan) {
+                            self.popup_cancel();
+                        }
+                        self.toasts.push(Toast::info("Baseline creation cancelled".to_string()));
+                    }
                 }
             }
             
@@ -437,36 +2005,93 @@ impl App {
         }
     }
     
+    pub fn move_change_up(&mut self) {
+        self.selected_change = self.selected_change.saturating_sub(1);
+        self.change_detail_scroll = 0;
+    }
+    
+    pub fn move_change_down(&mut self) {
+        if self.selected_change + 1 < self.change_rows.len() {
+            self.selected_change += 1;
+            self.change_detail_scroll = 0;
+        }
+    }
+    
+    /// Write the current comparison as JSON, CSV and Markdown into the
+    /// data dir's `reports` folder
+    pub fn export_comparison(&mut self) {
+        let Some(comparison) = &self.comparison else {
+            self.toasts.push(Toast::info("Nothing to export - compare baselines first".to_string()));
+            return;
+        };
+        
+        let stem = self.data_dir
+            .join("reports")
+            .join(format!("changes-{}-to-{}", comparison.from, comparison.to));
+        
+        let formats = [ReportFormat::Json, ReportFormat::Csv, ReportFormat::Markdown];
+        let result = formats.iter().try_for_each(|format| {
+            let text = crate::report::render(comparison, &self.packages, *format);
+            crate::baseline::write_atomic(&stem.with_extension(format.extension()), text.as_bytes())
+        });
+        
+        match result {
+            Ok(()) => self.toasts.push(Toast::success(format!("Exported {}.{{json,csv,md}}", stem.display()))),
+            Err(e) => {
+                self.popup = Some(tui_components::Popup::error(
+                    "Export Failed".to_string(),
+                    format!("Could not write report: {}", e),
+                ));
+            }
+        }
+    }
+    
     pub fn move_baseline_up(&mut self) {
+        if let Some(browser) = &mut self.baseline_browser {
+            browser.move_up();
+            return;
+        }
         if self.selected_baseline > 0 {
             self.selected_baseline -= 1;
         }
     }
     
     pub fn move_baseline_down(&mut self) {
+        if let Some(browser) = &mut self.baseline_browser {
+            browser.move_down();
+            return;
+        }
         if self.selected_baseline < self.baselines.len().saturating_sub(1) {
             self.selected_baseline += 1;
         }
     }
 
     pub fn switch_to_next_tab(&mut self) {
-        self.current_view = match self.current_view {
+        let next = match self.current_view {
             ViewMode::Dashboard => ViewMode::Changes,
             ViewMode::Changes => ViewMode::Baselines,
-            ViewMode::Baselines => ViewMode::Dashboard,
+            ViewMode::Baselines => ViewMode::Timeline,
+            ViewMode::Timeline => ViewMode::Dashboard,
         };
+        self.set_view(next);
     }
 
     pub fn switch_to_previous_tab(&mut self) {
-        self.current_view = match self.current_view {
-            ViewMode::Dashboard => ViewMode::Baselines,
+        let previous = match self.current_view {
+            ViewMode::Dashboard => ViewMode::Timeline,
             ViewMode::Changes => ViewMode::Dashboard,
             ViewMode::Baselines => ViewMode::Changes,
+            ViewMode::Timeline => ViewMode::Baselines,
         };
+        self.set_view(previous);
     }
 
     pub fn set_view(&mut self, view: ViewMode) {
         self.current_view = view;
+        // The journal and package log change outside the app
+        if view == ViewMode::Timeline {
+            self.refresh_timeline();
+        }
     }
 
     pub fn get_tab_items(&self) -> Vec<(String, ViewMode)> {
@@ -474,6 +2099,7 @@ impl App {
             ("DASHBOARD".to_string(), ViewMode::Dashboard),
             ("CHANGES".to_string(), ViewMode::Changes),
             ("BASELINES".to_string(), ViewMode::Baselines),
+            ("TIMELINE".to_string(), ViewMode::Timeline),
         ]
     }
 }
@@ -0,0 +1,467 @@
+// Portable baseline archives (tar + zstd) for moving baselines between
+// machines and keeping offline copies
+use crate::baseline::Baseline;
+use crate::index::BaselineIndex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Identifies the archive layout; bump the number on incompatible changes
+pub const ARCHIVE_SCHEMA: &str = "chamon.archive/1";
+const MANIFEST_NAME: &str = "manifest.json";
+const BASELINES_DIR: &str = "baselines";
+
+/// First entry of every archive: what it holds and how to check it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub schema: String,
+    pub created_at: String,
+    pub host: String,
+    pub chamon_version: String,
+    /// Version of the baseline that was exported
+    pub target: String,
+    /// Whether the chain was collapsed into a single standalone baseline
+    pub flattened: bool,
+    /// In chain order, initial baseline first
+    pub baselines: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub filename: String,
+    pub version: String,
+    pub parent: Option<String>,
+    pub is_delta: bool,
+    /// The baseline's own digest (see integrity.rs)
+    pub digest: Option<String>,
+    /// SHA-256 of the file as stored in the archive
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// Write `filename`'s baseline and everything it builds on to `output`. With
+/// `flatten` the chain is collapsed into one standalone baseline instead.
+pub fn export(data_dir: &Path, filename: &str, output: &Path, flatten: bool) -> io::Result<Manifest> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let target = index.get(filename).cloned().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("Unknown baseline {}", filename))
+    })?;
+
+    // (file name, contents) in chain order
+    let files: Vec<(String, Vec<u8>)> = if flatten {
+        let mut baseline = Baseline::load(data_dir, filename)?;
+        baseline.files = crate::compare::materialize(data_dir, &index, filename)?;
+        baseline.file_count = baseline.files.len();
+        baseline.removed.clear();
+        baseline.is_delta = false;
+        baseline.parent = None;
+        crate::integrity::seal(&mut baseline, data_dir)?;
+        vec![(baseline.filename(), serde_json::to_vec_pretty(&baseline)?)]
+    } else {
+        index.chain(filename)?.iter()
+            .map(|entry| Ok((entry.filename.clone(), fs::read(data_dir.join(BASELINES_DIR).join(&entry.filename))?)))
+            .collect::<io::Result<_>>()?
+    };
+
+    let mut manifest = Manifest {
+        schema: ARCHIVE_SCHEMA.to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+        host: target.host.clone(),
+        chamon_version: env!("CARGO_PKG_VERSION").to_string(),
+        target: target.version.clone(),
+        flattened: flatten,
+        baselines: Vec::new(),
+    };
+    for (name, bytes) in &files {
+        let baseline: Baseline = serde_json::from_slice(bytes)?;
+        manifest.baselines.push(ManifestEntry {
+            filename: name.clone(),
+            version: baseline.version,
+            parent: baseline.parent,
+            is_delta: baseline.is_delta,
+            digest: baseline.digest,
+            sha256: sha256_hex(bytes),
+            size: bytes.len() as u64,
+        });
+    }
+
+    // Written beside the target and renamed, so a failed export leaves no
+    // truncated archive behind
+    let mut tmp_name = output.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = output.with_file_name(tmp_name);
+    {
+        let encoder = zstd::Encoder::new(fs::File::create(&tmp_path)?, 0)?;
+        let mut builder = tar::Builder::new(encoder);
+        append(&mut builder, MANIFEST_NAME, &serde_json::to_vec_pretty(&manifest)?)?;
+        for (name, bytes) in &files {
+            append(&mut builder, &format!("{}/{}", BASELINES_DIR, name), bytes)?;
+        }
+        builder.into_inner()?.finish()?.sync_all()?;
+    }
+    fs::rename(&tmp_path, output)?;
+    Ok(manifest)
+}
+
+fn append<W: io::Write>(builder: &mut tar::Builder<W>, path: &str, bytes: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, path, bytes)
+}
+
+/// What an import did, or would do when it has conflicts
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub manifest: Option<Manifest>,
+    /// Versions written to the data dir (or that would be, on a dry run)
+    pub imported: Vec<String>,
+    /// Versions already present with the same digest
+    pub skipped: Vec<String>,
+    /// Reasons the import was refused; nothing is written when there are any
+    pub conflicts: Vec<String>,
+    /// File name of the reference baseline the archive was stored as, when
+    /// it came from a different chain
+    pub reference: Option<String>,
+}
+
+/// Import an archive into `data_dir`. The archive is checked against its
+/// manifest and each baseline against its digest first. An archive from
+/// another chain (another machine, or a flattened export) is stored as a
+/// single reference baseline beside the local chain. Otherwise baselines
+/// that clash with stored ones (same file, different contents) or would
+/// fork the local chain are reported as conflicts and nothing is imported.
+pub fn import(data_dir: &Path, archive: &Path, dry_run: bool) -> io::Result<ImportReport> {
+    let (manifest, baselines) = read_archive(archive)?;
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    if let (Some(initial), Some(root)) = (index.initial(), baselines.first()) {
+        let local = Baseline::load(data_dir, &initial.filename)?;
+        let same_chain = !root.reference && local.digest.is_some() && local.digest == root.digest;
+        if !same_chain {
+            return import_reference(data_dir, &index, manifest, baselines, dry_run);
+        }
+    }
+    let mut report = ImportReport::default();
+    let archived: Vec<&str> = manifest.baselines.iter().map(|e| e.filename.as_str()).collect();
+
+    let mut to_import = Vec::new();
+    for baseline in baselines {
+        let filename = baseline.filename();
+        if let Some(existing) = index.get(&filename) {
+            let stored = Baseline::load(data_dir, &existing.filename)?;
+            if stored.digest.is_some() && stored.digest == baseline.digest {
+                report.skipped.push(baseline.version.clone());
+            } else {
+                report.conflicts.push(format!(
+                    "{} already exists here ({}) with different contents",
+                    filename, existing.version,
+                ));
+            }
+            continue;
+        }
+
+        // Deltas are applied in chain order, so two children of the same
+        // parent would leave one of them unreachable
+        if let Some(parent) = &baseline.parent {
+            let in_archive = manifest.baselines.iter().any(|e| &e.version == parent);
+            let local = index.entries.iter().find(|e| &e.version == parent);
+            if !in_archive {
+                let Some(local) = local else {
+                    report.conflicts.push(format!("{} builds on {}, which is in neither place", baseline.version, parent));
+                    continue;
+                };
+                if Baseline::load(data_dir, &local.filename)?.digest != baseline.parent_digest {
+                    report.conflicts.push(format!("{} was not taken against the local {}", baseline.version, parent));
+                    continue;
+                }
+            }
+            if let Some(sibling) = index.entries.iter().find(|e| {
+                e.is_delta && e.parent.as_ref() == Some(parent) && !archived.contains(&e.filename.as_str())
+            }) {
+                report.conflicts.push(format!(
+                    "{} and the local {} were both taken against {}",
+                    baseline.version, sibling.version, parent,
+                ));
+                continue;
+            }
+        }
+        to_import.push(baseline);
+    }
+
+    if !report.conflicts.is_empty() {
+        report.manifest = Some(manifest);
+        return Ok(report);
+    }
+    for mut baseline in to_import {
+        if !dry_run {
+            crate::integrity::adopt_signature(&mut baseline)?;
+            baseline.save(data_dir)?;
+        }
+        report.imported.push(baseline.version);
+    }
+    report.manifest = Some(manifest);
+    Ok(report)
+}
+
+/// Collapse an archive's chain into one standalone reference baseline and
+/// store it under its own name, so it can be compared against but never
+/// becomes part of the local chain
+fn import_reference(
+    data_dir: &Path,
+    index: &BaselineIndex,
+    manifest: Manifest,
+    mut baselines: Vec<Baseline>,
+    dry_run: bool,
+) -> io::Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let mut reference = baselines.pop()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "The archive holds no baselines"))?;
+
+    let mut files = HashMap::new();
+    for baseline in baselines.into_iter().chain(std::iter::once(reference.clone())) {
+        for path in &baseline.removed {
+            files.remove(path);
+        }
+        files.extend(baseline.files);
+    }
+    reference.files = files;
+    reference.file_count = reference.files.len();
+    reference.removed.clear();
+    reference.is_delta = false;
+    reference.parent = None;
+    reference.parent_digest = None;
+    reference.reference = true;
+
+    let filename = reference.filename();
+    if let Some(existing) = index.get(&filename) {
+        let stored = Baseline::load(data_dir, &existing.filename)?;
+        if crate::integrity::digest(&stored) == crate::integrity::digest(&reference) {
+            report.skipped.push(reference.version);
+        } else {
+            report.conflicts.push(format!("{} already exists here with different contents", filename));
+        }
+    } else if let Some(local) = index.entries.iter().find(|e| !e.reference && e.version == reference.version) {
+        report.conflicts.push(format!("{} is also the version of the local {}", reference.version, local.filename));
+    } else {
+        if !dry_run {
+            crate::integrity::seal(&mut reference, data_dir)?;
+            reference.save(data_dir)?;
+        }
+        report.imported.push(reference.version);
+    }
+    report.reference = Some(filename);
+    report.manifest = Some(manifest);
+    Ok(report)
+}
+
+/// Read an archive and check it: manifest schema, file hashes, names, each
+/// baseline's digest and the digest links between them
+fn read_archive(archive: &Path) -> io::Result<(Manifest, Vec<Baseline>)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    let decoder = zstd::Decoder::new(fs::File::open(archive)?)?;
+    let mut entries: HashMap<String, Vec<u8>> = HashMap::new();
+    for entry in tar::Archive::new(decoder).entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        entries.insert(path, bytes);
+    }
+
+    let manifest: Manifest = serde_json::from_slice(
+        entries.get(MANIFEST_NAME).ok_or_else(|| invalid(format!("{} has no manifest", archive.display())))?,
+    )?;
+    if manifest.schema != ARCHIVE_SCHEMA {
+        return Err(invalid(format!("Unsupported archive schema {}", manifest.schema)));
+    }
+
+    let mut baselines: Vec<Baseline> = Vec::new();
+    for item in &manifest.baselines {
+        // Only plain baseline file names, so nothing lands outside the data dir
+        let plain = item.filename.starts_with("baseline-")
+            && item.filename.ends_with(".json")
+            && !item.filename.contains(['/', '\\']);
+        if !plain {
+            return Err(invalid(format!("Invalid baseline name {:?} in manifest", item.filename)));
+        }
+        let bytes = entries.get(&format!("{}/{}", BASELINES_DIR, item.filename))
+            .ok_or_else(|| invalid(format!("{} is listed but missing", item.filename)))?;
+        if sha256_hex(bytes) != item.sha256 {
+            return Err(invalid(format!("{} does not match the manifest; the archive is damaged", item.filename)));
+        }
+
+        let mut baseline: Baseline = serde_json::from_slice(bytes)?;
+        baseline.migrate_storage_keys();
+        // The manifest records each digest too, so removing one from the
+        // baseline alone doesn't pass for an unsealed baseline
+        if baseline.filename() != item.filename || baseline.digest != item.digest || !crate::integrity::is_intact(&baseline) {
+            return Err(invalid(format!("{} does not match its digest", item.filename)));
+        }
+        if let Some(parent) = &baseline.parent {
+            let parent_digest = baselines.iter().find(|b| &b.version == parent).map(|b| &b.digest);
+            if parent_digest.is_some_and(|digest| *digest != baseline.parent_digest) {
+                return Err(invalid(format!("{} is not chained to {}", item.filename, parent)));
+            }
+        }
+        baselines.push(baseline);
+    }
+    Ok((manifest, baselines))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::{FileEntry, TrackMode};
+    use std::path::PathBuf;
+
+    /// A fresh temp dir. The signing key points at the same missing file as
+    /// in the integrity tests, so nothing is signed.
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::set_var("CHAMON_SIGNING_KEY", std::env::temp_dir().join("chamon-test-no-signing-key"));
+        let dir = std::env::temp_dir().join(format!("chamon-test-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn baseline(version: &str, parent: Option<&str>, path: &str, size: u64) -> Baseline {
+        let mut baseline = Baseline::new("/".to_string(), "/".to_string());
+        baseline.version = version.to_string();
+        baseline.is_delta = parent.is_some();
+        baseline.parent = parent.map(str::to_string);
+        baseline.add_file(path.to_string(), FileEntry {
+            path: path.to_string(),
+            track_mode: TrackMode::Existence { size, modified: 0, permissions: 0o100644, owner: 0, group: 0 },
+        });
+        baseline
+    }
+
+    /// Seal and save `baseline` into `data_dir`
+    fn store(data_dir: &Path, mut baseline: Baseline) -> Baseline {
+        crate::integrity::seal(&mut baseline, data_dir).unwrap();
+        baseline.save(data_dir).unwrap();
+        baseline
+    }
+
+    /// An archive of a sealed two-baseline chain, in `dir`
+    fn exported(dir: &Path) -> PathBuf {
+        let data_dir = dir.join("data");
+        store(&data_dir, baseline("20250101-000000", None, "/etc/hosts", 1));
+        let delta = store(&data_dir, baseline("20250102-000000", Some("20250101-000000"), "/etc/hostname", 2));
+        let archive = dir.join("chain.tar.zst");
+        export(&data_dir, &delta.filename(), &archive, false).unwrap();
+        archive
+    }
+
+    /// Rewrite `archive` after letting `edit` change its manifest and
+    /// baseline files (by file name)
+    fn rewrite(archive: &Path, edit: impl FnOnce(&mut Manifest, &mut Vec<(String, Vec<u8>)>)) {
+        let (mut manifest, baselines) = read_archive(archive).unwrap();
+        let mut files: Vec<(String, Vec<u8>)> = baselines.iter()
+            .map(|b| (b.filename(), serde_json::to_vec_pretty(b).unwrap()))
+            .collect();
+        edit(&mut manifest, &mut files);
+
+        let encoder = zstd::Encoder::new(fs::File::create(archive).unwrap(), 0).unwrap();
+        let mut builder = tar::Builder::new(encoder);
+        append(&mut builder, MANIFEST_NAME, &serde_json::to_vec_pretty(&manifest).unwrap()).unwrap();
+        for (name, bytes) in &files {
+            append(&mut builder, &format!("{}/{}", BASELINES_DIR, name), bytes).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// Replace the `index`th baseline file, keeping the manifest's hash of
+    /// it current so only the baseline checks can object
+    fn replace(manifest: &mut Manifest, files: &mut [(String, Vec<u8>)], index: usize, baseline: &Baseline) {
+        let bytes = serde_json::to_vec_pretty(baseline).unwrap();
+        manifest.baselines[index].sha256 = sha256_hex(&bytes);
+        files[index].1 = bytes;
+    }
+
+    fn read_error(archive: &Path) -> String {
+        read_archive(archive).expect_err("archive should be refused").to_string()
+    }
+
+    #[test]
+    fn exported_chain_reads_back() {
+        let dir = temp_dir("roundtrip");
+        let (manifest, baselines) = read_archive(&exported(&dir)).unwrap();
+        assert_eq!(manifest.target, "20250102-000000");
+        let versions: Vec<&str> = baselines.iter().map(|b| b.version.as_str()).collect();
+        assert_eq!(versions, ["20250101-000000", "20250102-000000"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_damaged_files_and_unknown_schemas() {
+        let dir = temp_dir("damaged");
+        let archive = exported(&dir);
+        rewrite(&archive, |_, files| files[1].1.extend_from_slice(b"\n"));
+        assert!(read_error(&archive).contains("does not match the manifest"));
+
+        let archive = exported(&dir);
+        rewrite(&archive, |manifest, _| manifest.schema = "chamon.archive/99".to_string());
+        assert!(read_error(&archive).contains("Unsupported archive schema"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_names_outside_the_baselines_dir() {
+        let dir = temp_dir("names");
+        let archive = exported(&dir);
+        // Checked before the file is looked up, so it needn't be in the archive
+        rewrite(&archive, |manifest, _| manifest.baselines[1].filename = "baseline-../../evil.json".to_string());
+        assert!(read_error(&archive).contains("Invalid baseline name"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_edited_and_stripped_baselines() {
+        let dir = temp_dir("edited");
+        let archive = exported(&dir);
+        rewrite(&archive, |manifest, files| {
+            let mut delta: Baseline = serde_json::from_slice(&files[1].1).unwrap();
+            delta.files.values_mut().for_each(|e| e.track_mode = TrackMode::Existence {
+                size: 99, modified: 0, permissions: 0o100644, owner: 0, group: 0,
+            });
+            replace(manifest, files, 1, &delta);
+        });
+        assert!(read_error(&archive).contains("does not match its digest"));
+
+        // Without its digest, and the parent digest that would give it away
+        let archive = exported(&dir);
+        rewrite(&archive, |manifest, files| {
+            let mut delta: Baseline = serde_json::from_slice(&files[1].1).unwrap();
+            delta.digest = None;
+            delta.parent_digest = None;
+            replace(manifest, files, 1, &delta);
+        });
+        assert!(read_error(&archive).contains("does not match its digest"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_a_swapped_parent() {
+        let dir = temp_dir("swapped");
+        let archive = exported(&dir);
+        // A different initial baseline, properly sealed and listed
+        let other = store(&dir.join("other"), baseline("20250101-000000", None, "/etc/hosts", 7));
+        rewrite(&archive, |manifest, files| {
+            manifest.baselines[0].digest = other.digest.clone();
+            replace(manifest, files, 0, &other);
+        });
+        assert!(read_error(&archive).contains("is not chained to 20250101-000000"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,5 +1,7 @@
 // Baseline creation and management
 use crate::config::BaselineConfig;
+use crate::index::{BaselineIndex, IndexEntry};
+use crate::restore::is_under;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -19,7 +21,35 @@ pub struct Baseline {
     pub remap_to: String,
     pub file_count: usize,
     pub is_delta: bool,
+    /// Imported from another machine as a standalone baseline to compare
+    /// against; it stands outside the local chain (see archive.rs)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reference: bool,
+    /// Version of the baseline this delta was taken against
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub host: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Pinned baselines are protected from deletion and pruning
+    #[serde(default)]
+    pub pinned: bool,
     pub files: HashMap<String, FileEntry>,
+    /// Paths present in the parent that no longer exist (deltas only)
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// SHA-256 of the recorded state, including `parent_digest` (see integrity.rs)
+    #[serde(default)]
+    pub digest: Option<String>,
+    /// The parent's digest at the time this delta was taken
+    #[serde(default)]
+    pub parent_digest: Option<String>,
+    /// ed25519 signature of `digest`, hex-encoded, when a signing key exists
+    #[serde(default)]
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,6 +79,72 @@ pub enum TrackMode {
     },
 }
 
+impl TrackMode {
+    pub fn size(&self) -> u64 {
+        match self {
+            TrackMode::Content { size, .. } | TrackMode::Existence { size, .. } => *size,
+        }
+    }
+    
+    pub fn modified(&self) -> u64 {
+        match self {
+            TrackMode::Content { modified, .. } | TrackMode::Existence { modified, .. } => *modified,
+        }
+    }
+    
+    pub fn permissions(&self) -> u32 {
+        match self {
+            TrackMode::Content { permissions, .. } | TrackMode::Existence { permissions, .. } => *permissions,
+        }
+    }
+    
+    pub fn owner(&self) -> u32 {
+        match self {
+            TrackMode::Content { owner, .. } | TrackMode::Existence { owner, .. } => *owner,
+        }
+    }
+    
+    pub fn group(&self) -> u32 {
+        match self {
+            TrackMode::Content { group, .. } | TrackMode::Existence { group, .. } => *group,
+        }
+    }
+    
+    /// Stored text content, for content-tracked files
+    pub fn content(&self) -> Option<&str> {
+        match self {
+            TrackMode::Content { content, .. } => Some(content),
+            TrackMode::Existence { .. } => None,
+        }
+    }
+    
+    pub fn checksum(&self) -> Option<&str> {
+        match self {
+            TrackMode::Content { checksum, .. } => Some(checksum),
+            TrackMode::Existence { .. } => None,
+        }
+    }
+    
+    /// Whether two records describe the same file state. Content-tracked
+    /// files compare by checksum, so touching a file isn't a change;
+    /// existence-only files fall back to size and modification time.
+    pub fn same_as(&self, other: &TrackMode) -> bool {
+        let metadata_same = self.permissions() == other.permissions()
+            && self.owner() == other.owner()
+            && self.group() == other.group();
+        
+        metadata_same && self.same_data(other)
+    }
+    
+    /// Whether the file's data is unchanged, ignoring mode and ownership
+    pub fn same_data(&self, other: &TrackMode) -> bool {
+        match (self.checksum(), other.checksum()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.size() == other.size() && self.modified() == other.modified(),
+        }
+    }
+}
+
 impl Baseline {
     pub fn new(scan_path: String, remap_to: String) -> Self {
         let now = SystemTime::now()
@@ -67,23 +163,46 @@ impl Baseline {
             remap_to,
             file_count: 0,
             is_delta: false,
+            reference: false,
+            parent: None,
+            host: local_hostname(),
+            label: None,
+            notes: None,
+            pinned: false,
             files: HashMap::new(),
+            removed: Vec::new(),
+            digest: None,
+            parent_digest: None,
+            signature: None,
         }
     }
     
-    pub fn save(&self, data_dir: &Path) -> io::Result<PathBuf> {
-        let baselines_dir = data_dir.join("baselines");
-        fs::create_dir_all(&baselines_dir)?;
-        
-        let filename = if self.is_delta {
+    /// File name this baseline is stored under in `data/baselines`.
+    /// References are named after their host, so several machines' can sit
+    /// beside the local chain.
+    pub fn filename(&self) -> String {
+        if self.reference {
+            let host: String = self.host.chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+                .collect();
+            format!("baseline-ref-{}-{}.json", host, self.version)
+        } else if self.is_delta {
             format!("baseline-{}.json", self.version)
         } else {
             "baseline-initial.json".to_string()
-        };
+        }
+    }
+    
+    /// Write the baseline and record it in the baseline index
+    pub fn save(&self, data_dir: &Path) -> io::Result<PathBuf> {
+        let baselines_dir = data_dir.join("baselines");
+        fs::create_dir_all(&baselines_dir)?;
         
-        let file_path = baselines_dir.join(filename);
+        let file_path = baselines_dir.join(self.filename());
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(&file_path, json)?;
+        write_atomic(&file_path, json.as_bytes())?;
+        
+        BaselineIndex::record(data_dir, IndexEntry::from_baseline(self, json.len() as u64))?;
         
         Ok(file_path)
     }
@@ -91,7 +210,35 @@ impl Baseline {
     pub fn load(data_dir: &Path, filename: &str) -> io::Result<Self> {
         let file_path = data_dir.join("baselines").join(filename);
         let content = fs::read_to_string(&file_path)?;
-        let baseline: Baseline = serde_json::from_str(&content)?;
+        let mut baseline: Baseline = serde_json::from_str(&content)?;
+        baseline.migrate_storage_keys();
+        Ok(baseline)
+    }
+    
+    /// Older scans keyed files by their path relative to whichever directory
+    /// the scan worker started from (e.g. "ssh/sshd_config"), so they would
+    /// diff as entirely added/removed against current ones. Re-key them by
+    /// the full remapped path every entry records. Sealed baselines always
+    /// use full paths and are left alone, so their digest still matches.
+    pub(crate) fn migrate_storage_keys(&mut self) {
+        if self.digest.is_some() || self.files.iter().all(|(key, entry)| *key == entry.path) {
+            return;
+        }
+        self.files = std::mem::take(&mut self.files)
+            .into_values()
+            .map(|entry| (entry.path.clone(), entry))
+            .collect();
+        self.file_count = self.files.len();
+    }
+    
+    /// Change a stored baseline's label, notes or pinned flag and save it back
+    pub fn update_metadata<F>(data_dir: &Path, filename: &str, update: F) -> io::Result<Self>
+    where
+        F: FnOnce(&mut Baseline),
+    {
+        let mut baseline = Self::load(data_dir, filename)?;
+        update(&mut baseline);
+        baseline.save(data_dir)?;
         Ok(baseline)
     }
     
@@ -99,6 +246,109 @@ impl Baseline {
         self.files.insert(path.clone(), entry);
         self.file_count = self.files.len();
     }
+    
+    /// Fold an older delta into this one (its successor) so this delta can
+    /// stand in for both: applying the result to `older`'s parent yields the
+    /// same state as applying `older` and then `self`.
+    pub fn squash_from(&mut self, older: &Baseline) {
+        let mut files = older.files.clone();
+        for path in &self.removed {
+            files.remove(path);
+        }
+        files.extend(self.files.drain());
+        
+        let mut removed: Vec<String> = older.removed.iter()
+            .chain(self.removed.iter())
+            .filter(|path| !files.contains_key(*path))
+            .cloned()
+            .collect();
+        removed.sort();
+        removed.dedup();
+        
+        self.files = files;
+        self.file_count = self.files.len();
+        self.removed = removed;
+        self.parent = older.parent.clone();
+    }
+    
+    /// Fold a stored delta into the delta taken after it, then delete it.
+    /// Returns the file name of the successor that absorbed it.
+    pub fn merge_into_successor(data_dir: &Path, filename: &str) -> io::Result<String> {
+        let index = BaselineIndex::load_or_rebuild(data_dir)?;
+        let successor = index.successor(filename)
+            .map(|e| e.filename.clone())
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} has no later delta to merge into", filename),
+            ))?;
+        
+        let older = Self::load(data_dir, filename)?;
+        if !older.is_delta {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The initial baseline cannot be merged into a delta",
+            ));
+        }
+        
+        let mut newer = Self::load(data_dir, &successor)?;
+        // Re-sealing would vouch for whatever was edited into either file or
+        // into anything built on them, so check all of it before writing
+        for baseline in [&older, &newer] {
+            crate::integrity::ensure_intact(data_dir, &index, baseline, "merge")?;
+        }
+        crate::integrity::ensure_descendants_intact(data_dir, &newer.version)?;
+        newer.squash_from(&older);
+        // Its contents and parent changed, so it and everything built on it
+        // get new digests
+        crate::integrity::seal(&mut newer, data_dir)?;
+        newer.save(data_dir)?;
+        Self::remove_file(data_dir, filename)?;
+        crate::integrity::reseal_descendants(data_dir, &newer.version)?;
+        
+        Ok(successor)
+    }
+    
+    /// Delete a stored baseline file and drop it from the index. File
+    /// contents are stored inline in the baseline, so nothing else is left
+    /// behind. Callers check `BaselineIndex::delete_impact` first.
+    pub fn remove_file(data_dir: &Path, filename: &str) -> io::Result<()> {
+        let file_path = data_dir.join("baselines").join(filename);
+        match fs::remove_file(&file_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        BaselineIndex::forget(data_dir, filename)
+    }
+}
+
+/// Write a file atomically: write to a sibling temp file, sync, then rename
+/// over the target so readers never observe a partially written file
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    use std::io::Write;
+    
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Hostname of the machine the baseline is taken on
+pub(crate) fn local_hostname() -> String {
+    fs::read_to_string("/etc/hostname")
+        .ok()
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// Remap a physical path to a logical path
@@ -163,7 +413,7 @@ pub fn scan_file(
     
     // Check if in existence-only directory
     let is_existence_only = config.existence_only_directories.iter()
-        .any(|dir| remapped_path.starts_with(dir));
+        .any(|dir| is_under(&remapped_path, dir));
     
     // Check if extension is existence-only
     let is_existence_ext = if let Some(ext) = file_path.extension() {
@@ -240,17 +490,139 @@ pub fn scan_file(
 fn should_exclude(physical_path: &str, scan_path: &str, remap_to: &str, config: &BaselineConfig) -> bool {
     let remapped = remap_path(physical_path, scan_path, remap_to);
     config.exclude_directories.iter()
-        .any(|exclude| remapped.starts_with(exclude))
+        .any(|exclude| is_under(&remapped, exclude))
+}
+
+/// Files found by a filesystem scan, keyed by their remapped path
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    pub scan_path: String,
+    pub remap_to: String,
+    pub files: HashMap<String, FileEntry>,
 }
 
+/// Cancel and pause switches for a running scan, shared between the thread
+/// that owns the scan and its workers
+#[derive(Debug, Clone, Default)]
+pub struct ScanControl {
+    cancel: Arc<AtomicBool>,
+    pause: Arc<AtomicBool>,
+}
+
+impl ScanControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Stop the scan; a paused scan stops too. Nothing is saved.
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+    
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+    
+    pub fn set_paused(&self, paused: bool) {
+        self.pause.store(paused, Ordering::Relaxed);
+    }
+    
+    pub fn is_paused(&self) -> bool {
+        self.pause.load(Ordering::Relaxed)
+    }
+    
+    /// Called by workers between files: blocks while paused and returns
+    /// whether the scan was cancelled
+    fn checkpoint(&self) -> bool {
+        while self.is_paused() && !self.is_cancelled() {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        self.is_cancelled()
+    }
+}
+
+/// Scan and save the initial baseline
 pub fn create_initial_baseline<F>(
     scan_path: &str,
     remap_to: &str,
     data_dir: &Path,
     config: &BaselineConfig,
-    cancel_flag: Arc<AtomicBool>,
+    control: ScanControl,
+    progress_callback: F,
+) -> io::Result<Baseline>
+where
+    F: FnMut(&str, usize, &str) + Send + Sync + 'static,
+{
+    let scan = scan_filesystem(scan_path, remap_to, config, control, progress_callback)?;
+    
+    let mut baseline = Baseline::new(scan.scan_path, scan.remap_to);
+    for (path, entry) in scan.files {
+        baseline.add_file(path, entry);
+    }
+    
+    crate::integrity::seal(&mut baseline, data_dir)?;
+    baseline.save(data_dir)?;
+    Ok(baseline)
+}
+
+/// Scan and save a delta against the newest baseline in the chain, holding
+/// only the files that were added or changed plus the paths removed since
+pub fn create_delta_baseline<F>(
+    scan_path: &str,
+    remap_to: &str,
+    data_dir: &Path,
+    config: &BaselineConfig,
+    control: ScanControl,
     progress_callback: F,
 ) -> io::Result<Baseline>
+where
+    F: FnMut(&str, usize, &str) + Send + Sync + 'static,
+{
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let head = index.head().ok_or_else(|| io::Error::new(
+        io::ErrorKind::NotFound,
+        "No initial baseline yet; create one first",
+    ))?;
+    
+    // The version is the time the scan starts, so a clash is known before
+    // spending a full scan on it
+    let mut baseline = Baseline::new(scan_path.to_string(), remap_to.to_string());
+    baseline.is_delta = true;
+    baseline.parent = Some(head.version.clone());
+    if index.entries.iter().any(|e| e.version == baseline.version) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("Baseline {} already exists; wait a second and try again", baseline.version),
+        ));
+    }
+    let previous = crate::compare::materialize(data_dir, &index, &head.filename)?;
+    
+    let scan = scan_filesystem(scan_path, remap_to, config, control, progress_callback)?;
+    baseline.scan_path = scan.scan_path;
+    baseline.remap_to = scan.remap_to;
+    
+    for change in crate::compare::diff_states(&previous, &scan.files) {
+        match change.new {
+            Some(entry) => baseline.add_file(change.path, entry),
+            None => baseline.removed.push(change.path),
+        }
+    }
+    
+    crate::integrity::seal(&mut baseline, data_dir)?;
+    baseline.save(data_dir)?;
+    Ok(baseline)
+}
+
+/// Walk `scan_path` in parallel and record every file, honoring the
+/// exclusion and existence-only rules. Paths are stored remapped to
+/// `remap_to`, so scans of a mounted image compare against the live system.
+pub fn scan_filesystem<F>(
+    scan_path: &str,
+    remap_to: &str,
+    config: &BaselineConfig,
+    control: ScanControl,
+    progress_callback: F,
+) -> io::Result<ScanResult>
 where
     F: FnMut(&str, usize, &str) + Send + Sync + 'static,
 {
@@ -277,8 +649,6 @@ where
         ));
     }
     
-    let mut baseline = Baseline::new(scan_path_normalized.to_string(), remap_to_normalized.to_string());
-    
     // Use thread pool for parallel scanning
     let num_threads = num_cpus::get().max(1);
     let pool = threadpool::ThreadPool::new(num_threads);
@@ -287,16 +657,26 @@ where
     let files = Arc::new(DashMap::new());
     let total_files = Arc::new(AtomicUsize::new(0));
     
-    // Get top-level directories for parallel scanning
+    // Get top-level directories for parallel scanning; files directly in
+    // the scan root are recorded here
     let mut top_level_dirs = Vec::new();
     if let Ok(entries) = fs::read_dir(&scan_path_buf) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.is_dir() {
-                if let Some(path_str) = path.to_str() {
-                    // Check exclusion against remapped path
-                    if !should_exclude(path_str, scan_path_normalized, remap_to_normalized, config) {
-                        top_level_dirs.push(path);
+            let Ok(metadata) = entry.metadata() else { continue };
+            if metadata.is_symlink() {
+                continue;
+            }
+            if let Some(path_str) = path.to_str() {
+                // Check exclusion against remapped path
+                if should_exclude(path_str, scan_path_normalized, remap_to_normalized, config) {
+                    continue;
+                }
+                if metadata.is_dir() {
+                    top_level_dirs.push(path);
+                } else if metadata.is_file() {
+                    if let Ok(file_entry) = scan_file(&path, scan_path_normalized, remap_to_normalized, config) {
+                        files.insert(file_entry.path.clone(), file_entry);
                     }
                 }
             }
@@ -333,13 +713,13 @@ where
     };
     
     for _ in 0..num_workers {
-        if cancel_flag.load(Ordering::Relaxed) {
+        if control.is_cancelled() {
             break;
         }
         
         let files_clone = Arc::clone(&files);
         let total_clone = Arc::clone(&total_files);
-        let cancel_clone = Arc::clone(&cancel_flag);
+        let control_clone = control.clone();
         let progress_clone = Arc::clone(&progress_cb);
         let tx_clone = tx.clone();
         let config_clone = config.clone();
@@ -368,7 +748,7 @@ where
             const MAX_CONSECUTIVE_EMPTY: u32 = 100; // After 1 second of empty checks, exit
             
             loop {
-                if cancel_clone.load(Ordering::Relaxed) {
+                if control_clone.checkpoint() {
                     break;
                 }
                 
@@ -431,10 +811,12 @@ where
                 let dir_name = dir.to_string_lossy().to_string();
                 let display_name = remap_path(&dir_name, &scan_path_str, &remap_to_str);
                 
-                // Track active worker for this directory
-                let worker_count = active_workers_clone.entry(display_name.clone())
-                    .or_insert_with(|| Arc::new(AtomicUsize::new(0)));
-                worker_count.fetch_add(1, Ordering::Relaxed);
+                // Track active worker for this directory. The entry guard
+                // must not outlive this statement: it holds the shard lock
+                // that the `get` below needs.
+                active_workers_clone.entry(display_name.clone())
+                    .or_insert_with(|| Arc::new(AtomicUsize::new(0)))
+                    .fetch_add(1, Ordering::Relaxed);
                 
                 // Notify worker started (only for top-level directories)
                 if top_level_dirs_for_worker.iter().any(|d| d == &dir) {
@@ -495,7 +877,7 @@ where
                     &mut local_files,
                     &file_count,
                     &config_clone,
-                    &cancel_clone,
+                    &control_clone,
                     &*callback,
                     &work_queue_clone,
                     &work_queue_size_clone,
@@ -551,7 +933,7 @@ where
     }
     
     // Check if cancelled
-    if cancel_flag.load(Ordering::Relaxed) {
+    if control.is_cancelled() {
         return Err(io::Error::new(io::ErrorKind::Interrupted, "Scan cancelled"));
     }
     
@@ -562,14 +944,15 @@ where
     }
     
     // Collect results
-    for entry in files.iter() {
-        baseline.add_file(entry.key().clone(), entry.value().clone());
-    }
-    
-    // Save baseline
-    baseline.save(data_dir)?;
+    let files = files.iter()
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
     
-    Ok(baseline)
+    Ok(ScanResult {
+        scan_path: scan_path_normalized.to_string(),
+        remap_to: remap_to_normalized.to_string(),
+        files,
+    })
 }
 
 fn walk_directory_worker_with_stealing(
@@ -580,7 +963,7 @@ fn walk_directory_worker_with_stealing(
     results: &mut HashMap<String, FileEntry>,
     file_count: &Arc<AtomicUsize>,
     config: &BaselineConfig,
-    cancel_flag: &Arc<AtomicBool>,
+    control: &ScanControl,
     progress_callback: &dyn Fn(&str),
     work_queue: &Arc<Mutex<Vec<PathBuf>>>,
     work_queue_size: &Arc<AtomicUsize>,
@@ -589,7 +972,7 @@ fn walk_directory_worker_with_stealing(
     config_for_queue: &BaselineConfig,
 ) -> io::Result<()>
 {
-    if cancel_flag.load(Ordering::Relaxed) {
+    if control.checkpoint() {
         return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
     }
     
@@ -609,7 +992,7 @@ fn walk_directory_worker_with_stealing(
     };
     
     for entry in entries {
-        if cancel_flag.load(Ordering::Relaxed) {
+        if control.checkpoint() {
             return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled"));
         }
         
@@ -641,7 +1024,7 @@ fn walk_directory_worker_with_stealing(
                 results,
                 file_count,
                 config,
-                cancel_flag,
+                control,
                 progress_callback,
                 work_queue,
                 work_queue_size,
@@ -653,16 +1036,9 @@ fn walk_directory_worker_with_stealing(
             // Scan file
             match scan_file(&path, scan_path, remap_to, config) {
                 Ok(file_entry) => {
-                    // Calculate relative path from root for storage key
-                    let relative_path = path.strip_prefix(root)
-                        .unwrap_or(&path)
-                        .to_string_lossy()
-                        .to_string();
-                    
-                    // Use remapped path for storage
-                    let storage_path = remap_path(&relative_path, scan_path, remap_to);
-                    
-                    results.insert(storage_path, file_entry);
+                    // Key by the full remapped path; paths relative to the
+                    // top-level directory collide across directories
+                    results.insert(file_entry.path.clone(), file_entry);
                     file_count.fetch_add(1, Ordering::Relaxed);
                     
                     // Report progress
@@ -679,3 +1055,42 @@ fn walk_directory_worker_with_stealing(
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn file(path: &str, size: u64) -> FileEntry {
+        FileEntry {
+            path: path.to_string(),
+            track_mode: TrackMode::Existence { size, modified: 0, permissions: 0o100644, owner: 0, group: 0 },
+        }
+    }
+    
+    fn delta(parent: &str, files: &[FileEntry], removed: &[&str]) -> Baseline {
+        let mut baseline = Baseline::new("/".to_string(), "/".to_string());
+        baseline.is_delta = true;
+        baseline.parent = Some(parent.to_string());
+        for entry in files {
+            baseline.add_file(entry.path.clone(), entry.clone());
+        }
+        baseline.removed = removed.iter().map(|p| p.to_string()).collect();
+        baseline
+    }
+    
+    #[test]
+    fn squash_keeps_the_newer_state_of_each_path() {
+        let older = delta("v1", &[file("/a", 1), file("/b", 1)], &["/x", "/y"]);
+        let mut newer = delta("v2", &[file("/b", 2), file("/x", 2)], &["/a", "/z"]);
+        newer.squash_from(&older);
+        
+        let mut paths: Vec<&str> = newer.files.keys().map(String::as_str).collect();
+        paths.sort();
+        // /a removed by the newer delta, /x re-added after the older removed it
+        assert_eq!(paths, ["/b", "/x"]);
+        assert_eq!(newer.files["/b"].track_mode.size(), 2);
+        assert_eq!(newer.removed, ["/a", "/y", "/z"]);
+        assert_eq!(newer.file_count, 2);
+        assert_eq!(newer.parent.as_deref(), Some("v1"));
+    }
+}
@@ -0,0 +1,390 @@
+// Changes browser: filtering a comparison, laying it out as a flat list
+// or a directory tree, and aligning both sides of a file for the split view
+use crate::baseline::FileEntry;
+use crate::compare::{ChangeKind, Comparison, FileChange, FileState};
+use crate::packages::PackageDb;
+use crate::restore::is_under;
+use crate::security::{self, Severity};
+use globset::{GlobBuilder, GlobMatcher};
+use similar::{DiffOp, TextDiff};
+use std::collections::{HashMap, HashSet};
+
+/// Which kinds of change the browser shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeFilter {
+    #[default]
+    All,
+    Added,
+    Modified,
+    Removed,
+    /// Modified files whose data is unchanged (mode or ownership only)
+    MetadataOnly,
+}
+
+impl TypeFilter {
+    /// The next filter in the cycle the view steps through
+    pub fn next(self) -> Self {
+        match self {
+            TypeFilter::All => TypeFilter::Added,
+            TypeFilter::Added => TypeFilter::Modified,
+            TypeFilter::Modified => TypeFilter::Removed,
+            TypeFilter::Removed => TypeFilter::MetadataOnly,
+            TypeFilter::MetadataOnly => TypeFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TypeFilter::All => "all",
+            TypeFilter::Added => "new",
+            TypeFilter::Modified => "modified",
+            TypeFilter::Removed => "deleted",
+            TypeFilter::MetadataOnly => "metadata-only",
+        }
+    }
+
+    fn matches(&self, change: &FileChange) -> bool {
+        match self {
+            TypeFilter::All => true,
+            TypeFilter::Added => change.kind == ChangeKind::Added,
+            TypeFilter::Modified => change.kind == ChangeKind::Modified,
+            TypeFilter::Removed => change.kind == ChangeKind::Removed,
+            TypeFilter::MetadataOnly => change.kind == ChangeKind::Modified && change.is_metadata_only(),
+        }
+    }
+}
+
+/// Step through severity thresholds: none, then low and up to high only
+pub fn next_severity(current: Option<Severity>) -> Option<Severity> {
+    match current {
+        None => Some(Severity::Low),
+        Some(Severity::Low) => Some(Severity::Medium),
+        Some(Severity::Medium) => Some(Severity::High),
+        Some(Severity::High) => None,
+    }
+}
+
+/// Filters for the change list; a change is shown when it passes all of them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeFilter {
+    pub kind: TypeFilter,
+    /// A plain path (itself and everything under it) or a glob. Globs that
+    /// don't start with `/` match at any depth.
+    pub path: Option<String>,
+    /// Only changes with a finding at least this severe
+    pub severity: Option<Severity>,
+    /// Only files owned by this package
+    pub package: Option<String>,
+}
+
+impl ChangeFilter {
+    pub fn is_active(&self) -> bool {
+        *self != ChangeFilter::default()
+    }
+
+    /// Short summary for the view title, e.g. `modified, /etc/**, >=medium`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.kind != TypeFilter::All {
+            parts.push(self.kind.label().to_string());
+        }
+        if let Some(path) = &self.path {
+            parts.push(path.clone());
+        }
+        if let Some(severity) = self.severity {
+            parts.push(format!(">={}", severity.label()));
+        }
+        if let Some(package) = &self.package {
+            parts.push(format!("pkg:{}", package));
+        }
+        parts.join(", ")
+    }
+
+    /// Indices into `comparison.changes` of the changes that pass, in order
+    pub fn apply(&self, comparison: &Comparison, packages: &PackageDb) -> Result<Vec<usize>, globset::Error> {
+        let path = self.path.as_deref().map(PathMatcher::new).transpose()?;
+
+        Ok(comparison.changes.iter()
+            .enumerate()
+            .filter(|(_, change)| self.kind.matches(change))
+            .filter(|(_, change)| path.as_ref().is_none_or(|p| p.matches(&change.path)))
+            .filter(|(_, change)| {
+                self.severity.is_none_or(|min| {
+                    security::max_severity(&security::assess(change)).is_some_and(|s| s >= min)
+                })
+            })
+            .filter(|(_, change)| {
+                self.package.as_deref().is_none_or(|package| packages.owner(&change.path) == Some(package))
+            })
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
+
+enum PathMatcher {
+    Prefix(String),
+    Glob(GlobMatcher),
+}
+
+impl PathMatcher {
+    fn new(pattern: &str) -> Result<Self, globset::Error> {
+        if !crate::drift::is_glob(pattern) {
+            return Ok(PathMatcher::Prefix(pattern.trim_end_matches('/').to_string()));
+        }
+        let pattern = if pattern.starts_with('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        Ok(PathMatcher::Glob(GlobBuilder::new(&pattern).literal_separator(true).build()?.compile_matcher()))
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        match self {
+            PathMatcher::Prefix(prefix) => prefix.is_empty() || is_under(path, prefix),
+            PathMatcher::Glob(glob) => glob.is_match(path),
+        }
+    }
+}
+
+/// One line of the change list
+#[derive(Debug, Clone, PartialEq)]
+pub enum Row {
+    /// A directory and the number of listed entries under it
+    Dir { path: String, depth: usize, count: usize, collapsed: bool },
+    /// An entry, by index into what is listed (`Comparison::changes` for
+    /// the Changes view)
+    File { index: usize, depth: usize },
+}
+
+pub fn flat_rows(indices: &[usize]) -> Vec<Row> {
+    indices.iter().map(|&index| Row::File { index, depth: 0 }).collect()
+}
+
+/// Changes nested under their directories. Children of a collapsed
+/// directory are left out. Relies on the changes being sorted by path, so
+/// everything under a directory is contiguous.
+pub fn tree_rows(comparison: &Comparison, indices: &[usize], collapsed: &HashSet<String>) -> Vec<Row> {
+    path_tree(|index| &comparison.changes[index].path, indices, collapsed)
+}
+
+/// Entries nested under their directories, `path_of` giving each listed
+/// index's path; the paths must be sorted
+fn path_tree<'a>(path_of: impl Fn(usize) -> &'a str, indices: &[usize], collapsed: &HashSet<String>) -> Vec<Row> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for &index in indices {
+        for dir in ancestors(path_of(index)) {
+            *counts.entry(dir).or_default() += 1;
+        }
+    }
+
+    let mut rows = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    for &index in indices {
+        let dirs = ancestors(path_of(index));
+        let common = open.iter().zip(&dirs).take_while(|(a, b)| a == b).count();
+
+        for (depth, dir) in dirs.iter().enumerate().skip(common) {
+            if dirs[..depth].iter().any(|d| collapsed.contains(*d)) {
+                break;
+            }
+            rows.push(Row::Dir {
+                path: dir.to_string(),
+                depth,
+                count: counts[dir],
+                collapsed: collapsed.contains(*dir),
+            });
+        }
+        if !dirs.iter().any(|d| collapsed.contains(*d)) {
+            rows.push(Row::File { index, depth: dirs.len() });
+        }
+        open = dirs;
+    }
+    rows
+}
+
+/// Directories above `path`, outermost first: `/etc/ssh/sshd_config` gives
+/// `/etc` and `/etc/ssh`
+fn ancestors(path: &str) -> Vec<&str> {
+    path.match_indices('/')
+        .map(|(pos, _)| pos)
+        .filter(|&pos| pos > 0)
+        .map(|pos| &path[..pos])
+        .collect()
+}
+
+/// Last component of a path, for tree rows
+pub fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().filter(|name| !name.is_empty()).unwrap_or(path)
+}
+
+/// The files a stored baseline describes, browsed as a tree from the
+/// Baselines view, with a read-only viewer for their stored content
+#[derive(Debug, Clone)]
+pub struct BaselineFiles {
+    /// The baseline being browsed
+    pub filename: String,
+    /// Its full file state, sorted by path
+    pub files: Vec<FileEntry>,
+    pub rows: Vec<Row>,
+    /// Index into `rows`
+    pub selected: usize,
+    pub collapsed: HashSet<String>,
+    /// The file open in the viewer, by index into `files`
+    pub viewing: Option<usize>,
+    /// First line shown in the viewer
+    pub scroll: u16,
+}
+
+impl BaselineFiles {
+    /// Browse `state`, starting with every top-level directory folded
+    pub fn new(filename: String, state: FileState) -> Self {
+        let mut files: Vec<FileEntry> = state.into_values().collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let collapsed = files.iter()
+            .filter_map(|entry| ancestors(&entry.path).first().map(|dir| dir.to_string()))
+            .collect();
+        let mut browser = Self {
+            filename,
+            files,
+            rows: Vec::new(),
+            selected: 0,
+            collapsed,
+            viewing: None,
+            scroll: 0,
+        };
+        browser.refresh_rows();
+        browser
+    }
+
+    fn refresh_rows(&mut self) {
+        let files = &self.files;
+        let indices: Vec<usize> = (0..files.len()).collect();
+        self.rows = path_tree(|index| &files[index].path, &indices, &self.collapsed);
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    /// The file under the cursor, if the cursor is on a file
+    pub fn selected_file(&self) -> Option<&FileEntry> {
+        match self.rows.get(self.selected)? {
+            Row::File { index, .. } => self.files.get(*index),
+            Row::Dir { .. } => None,
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        match self.viewing {
+            Some(_) => self.scroll = self.scroll.saturating_sub(1),
+            None => self.selected = self.selected.saturating_sub(1),
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        match self.viewing {
+            Some(_) => self.scroll = self.scroll.saturating_add(1),
+            None if self.selected + 1 < self.rows.len() => self.selected += 1,
+            None => {}
+        }
+    }
+
+    /// Put the cursor on `path`, unfolding its directories, and open it in
+    /// the viewer at `line` when its content is stored. Returns false when
+    /// the baseline doesn't describe the file.
+    pub fn reveal(&mut self, path: &str, line: Option<usize>) -> bool {
+        let Some(index) = self.files.iter().position(|entry| entry.path == path) else {
+            return false;
+        };
+        for dir in ancestors(path) {
+            self.collapsed.remove(dir);
+        }
+        self.refresh_rows();
+        if let Some(pos) = self.rows.iter().position(|r| matches!(r, Row::File { index: i, .. } if *i == index)) {
+            self.selected = pos;
+        }
+        self.viewing = None;
+        if self.files[index].track_mode.content().is_some() {
+            self.viewing = Some(index);
+            // A few lines of context above the match
+            self.scroll = line.map_or(0, |line| line.saturating_sub(4)) as u16;
+        }
+        true
+    }
+
+    /// Fold or unfold the directory under the cursor, or open the file in
+    /// the viewer. Returns false for a file whose content isn't stored.
+    pub fn open_selected(&mut self) -> bool {
+        match self.rows.get(self.selected) {
+            Some(Row::Dir { path, .. }) => {
+                let path = path.clone();
+                if !self.collapsed.remove(&path) {
+                    self.collapsed.insert(path);
+                }
+                self.refresh_rows();
+                true
+            }
+            Some(Row::File { index, .. }) => {
+                if self.files[*index].track_mode.content().is_none() {
+                    return false;
+                }
+                self.viewing = Some(*index);
+                self.scroll = 0;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+/// One row of the split view: a line of the old file next to the matching
+/// line of the new one. A side is None where the other side's line was
+/// added or removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitLine {
+    /// Line number (from 1) and text in the old file
+    pub old: Option<(usize, String)>,
+    /// Line number (from 1) and text in the new file
+    pub new: Option<(usize, String)>,
+}
+
+impl SplitLine {
+    pub fn is_change(&self) -> bool {
+        self.old.as_ref().map(|(_, text)| text) != self.new.as_ref().map(|(_, text)| text)
+    }
+}
+
+/// Both files' content aligned line by line, for content-tracked changes
+/// (an added or removed file counts as empty on its missing side).
+/// Replaced lines are paired up so a synchronized scroll keeps them side
+/// by side.
+pub fn split_lines(change: &FileChange) -> Option<Vec<SplitLine>> {
+    let old = match &change.old {
+        Some(entry) => entry.track_mode.content()?,
+        None => "",
+    };
+    let new = match &change.new {
+        Some(entry) => entry.track_mode.content()?,
+        None => "",
+    };
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let side = |lines: &[&str], index: usize| lines.get(index).map(|text| (index + 1, text.to_string()));
+
+    let mut rows = Vec::new();
+    for op in TextDiff::from_lines(old, new).ops() {
+        let (old_range, new_range) = match *op {
+            DiffOp::Equal { old_index, new_index, len } => (old_index..old_index + len, new_index..new_index + len),
+            DiffOp::Delete { old_index, old_len, new_index } => (old_index..old_index + old_len, new_index..new_index),
+            DiffOp::Insert { old_index, new_index, new_len } => (old_index..old_index, new_index..new_index + new_len),
+            DiffOp::Replace { old_index, old_len, new_index, new_len } => {
+                (old_index..old_index + old_len, new_index..new_index + new_len)
+            }
+        };
+        for offset in 0..old_range.len().max(new_range.len()) {
+            rows.push(SplitLine {
+                old: (offset < old_range.len()).then_some(old_range.start + offset).and_then(|i| side(&old_lines, i)),
+                new: (offset < new_range.len()).then_some(new_range.start + offset).and_then(|i| side(&new_lines, i)),
+            });
+        }
+    }
+    Some(rows)
+}
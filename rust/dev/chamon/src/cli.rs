@@ -0,0 +1,862 @@
+// Chamon command-line interface for headless use (cron, SSH, scripts)
+use crate::baseline::{Baseline, ScanControl};
+use crate::compare::{ChangeKind, Comparison};
+use crate::config::{Config, LoadedConfig};
+use crate::index::{BaselineIndex, DeleteImpact, IndexEntry};
+use crate::packages::PackageDb;
+use crate::report::ReportFormat;
+use crate::restore::RestoreAction;
+use crate::util::format_size;
+use clap::{Args, Parser, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(name = "chamon", version, about = "CHAMON - File change Monitor")]
+pub struct Cli {
+    /// Config file to use instead of ~/.config/chamon/config.yaml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    
+    /// Run a command without the TUI; launches the TUI when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create, list, inspect and delete baselines
+    Baseline {
+        #[command(subcommand)]
+        command: BaselineCommand,
+    },
+    /// Show the changes between two baselines
+    Diff {
+        /// Older baseline: `initial`, `latest`, a version or a label
+        from: String,
+        /// Newer baseline
+        to: String,
+        #[command(flatten)]
+        report: ReportArgs,
+    },
+    /// Show how the live filesystem differs from a baseline
+    Status {
+        /// Baseline to compare against (default: the active baseline)
+        #[arg(long)]
+        against: Option<String>,
+        #[command(flatten)]
+        report: ReportArgs,
+    },
+    /// Check the live filesystem for drift, for systemd timers and cron.
+    /// Exits 0 when clean, 1 when a drift rule matched and 2 on errors.
+    Check {
+        /// Baseline to check against (default: the active baseline)
+        #[arg(long)]
+        against: Option<String>,
+        /// Only log and publish the result; print nothing unless it fails
+        #[arg(long, short)]
+        quiet: bool,
+    },
+    /// Put files back to their state in a baseline
+    Restore {
+        /// Baseline to restore from
+        baseline: String,
+        /// Files or directories to restore
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// Show what would be restored without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Apply the retention policy, squashing pruned deltas into their successor
+    Prune {
+        /// Show what would be pruned without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Talk to the MQTT broker configured under `mqtt`
+    Mqtt {
+        #[command(subcommand)]
+        command: MqttCommand,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+/// Output options shared by the commands that produce a comparison
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Write a report instead of the plain change list
+    #[arg(long, value_enum)]
+    pub format: Option<ReportFormat>,
+    /// Write the output to a file instead of stdout
+    #[arg(long, short, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BaselineCommand {
+    /// Scan the filesystem and save the initial baseline
+    Create {
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// Scan the filesystem and save the changes since the latest baseline
+    Delta {
+        #[arg(long)]
+        label: Option<String>,
+    },
+    /// List baselines, newest first
+    List,
+    /// Delete a baseline
+    Delete {
+        baseline: String,
+        /// Merge a delta that later deltas depend on into its successor
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Write a baseline and the chain it builds on to a tar.zst archive
+    Export {
+        baseline: String,
+        /// Archive to write (default: chamon-<version>.tar.zst)
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Collapse the chain into one standalone baseline
+        #[arg(long)]
+        flatten: bool,
+    },
+    /// Import baselines from an archive written by `export`
+    Import {
+        archive: PathBuf,
+        /// Data dir to import into instead of the configured one, e.g. to
+        /// keep another machine's baselines apart
+        #[arg(long, value_name = "DIR")]
+        into: Option<PathBuf>,
+        /// Check the archive and report conflicts without importing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Check every baseline against its digest, hash chain and signature
+    Verify,
+    /// Generate the ed25519 key new baselines are signed with
+    Keygen {
+        /// Replace an existing key (baselines signed with it stop verifying)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show a baseline's metadata
+    Show {
+        baseline: String,
+        /// Also list the files it records
+        #[arg(long)]
+        files: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MqttCommand {
+    /// Publish the Home Assistant discovery configs and a test message
+    Test,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the config files, reporting problems with their line numbers
+    Check,
+}
+
+/// `chamon check` exit code when the check itself couldn't run; 1 means drift
+const CHECK_ERROR: u8 = 2;
+
+/// Run a headless command and return the process exit code. Config errors
+/// are fatal here: unattended runs shouldn't quietly fall back to defaults.
+pub fn run(command: Command, config_path: Option<&Path>) -> ExitCode {
+    let loaded = Config::load(config_path);
+    // Checking the config reports the errors itself rather than bailing out on them
+    if !matches!(command, Command::Config { .. }) {
+        if !loaded.errors.is_empty() {
+            for error in &loaded.errors {
+                eprintln!("chamon: config: {}", error);
+            }
+            return match command {
+                Command::Check { .. } => ExitCode::from(CHECK_ERROR),
+                _ => ExitCode::FAILURE,
+            };
+        }
+        for warning in &loaded.warnings {
+            eprintln!("chamon: config: warning: {}", warning);
+        }
+        // A broken drift rule would report drift (or miss it) on every run
+        if matches!(command, Command::Check { .. }) {
+            let diagnostics = crate::validate::validate(&loaded);
+            if crate::validate::has_errors(&diagnostics) {
+                for diagnostic in diagnostics.iter().filter(|d| d.severity == crate::validate::Severity::Error) {
+                    eprintln!("chamon: config: {}", diagnostic);
+                }
+                return ExitCode::from(CHECK_ERROR);
+            }
+        }
+    }
+    let config = &loaded.config;
+    let data_dir = config.data_dir();
+    
+    match command {
+        Command::Baseline { command } => match command {
+            BaselineCommand::Create { label } => create(config, &data_dir, false, label),
+            BaselineCommand::Delta { label } => create(config, &data_dir, true, label),
+            BaselineCommand::List => list(&data_dir),
+            BaselineCommand::Delete { baseline, merge } => delete(&data_dir, &baseline, merge),
+            BaselineCommand::Show { baseline, files } => show(&data_dir, &baseline, files),
+            BaselineCommand::Export { baseline, output, flatten } => export(&data_dir, &baseline, output, flatten),
+            BaselineCommand::Import { archive, into, dry_run } => {
+                import(into.as_deref().unwrap_or(&data_dir), &archive, dry_run)
+            }
+            BaselineCommand::Verify => verify(&data_dir),
+            BaselineCommand::Keygen { force } => keygen(force),
+        },
+        Command::Diff { from, to, report } => diff(config, &data_dir, &from, &to, &report),
+        Command::Status { against, report } => status(config, &data_dir, against.as_deref(), &report),
+        Command::Check { against, quiet } => check(config, &data_dir, against.as_deref(), quiet),
+        Command::Restore { baseline, paths, dry_run } => restore(&data_dir, &baseline, &paths, dry_run),
+        Command::Prune { dry_run } => prune(config, &data_dir, dry_run),
+        Command::Mqtt { command: MqttCommand::Test } => mqtt_test(config),
+        Command::Config { command: ConfigCommand::Check } => config_check(&loaded),
+    }
+}
+
+/// Print a warning for each baseline in the chains up to `filenames` that
+/// fails its integrity check. Returns whether any did.
+fn warn_if_tampered(data_dir: &Path, filenames: &[&str]) -> bool {
+    let mut tampered = false;
+    let mut seen = std::collections::HashSet::new();
+    for filename in filenames {
+        let issues = match crate::integrity::verify_chain(data_dir, filename) {
+            Ok(issues) => issues,
+            Err(e) => {
+                eprintln!("chamon: WARNING: could not verify {}: {}", filename, e);
+                continue;
+            }
+        };
+        for issue in issues.iter().filter(|i| i.problem.is_tampering()) {
+            if seen.insert(issue.to_string()) {
+                eprintln!("chamon: WARNING: {}", issue);
+                tampered = true;
+            }
+        }
+    }
+    tampered
+}
+
+/// Look up a baseline by the name given on the command line
+fn resolve(data_dir: &Path, name: &str) -> Result<IndexEntry, ExitCode> {
+    let index = BaselineIndex::load_or_rebuild(data_dir).map_err(|e| {
+        eprintln!("chamon: could not read baselines: {}", e);
+        ExitCode::FAILURE
+    })?;
+    index.resolve(name).cloned().ok_or_else(|| {
+        eprintln!("chamon: no baseline named {:?} (see `chamon baseline list`)", name);
+        ExitCode::FAILURE
+    })
+}
+
+fn create(config: &Config, data_dir: &Path, delta: bool, label: Option<String>) -> ExitCode {
+    let index = BaselineIndex::load_or_rebuild(data_dir).unwrap_or_default();
+    if !delta && index.initial().is_some() {
+        eprintln!("chamon: an initial baseline already exists; use `chamon baseline delta`");
+        return ExitCode::FAILURE;
+    }
+    
+    let baseline = &config.baseline;
+    eprintln!("scanning {} ...", baseline.scan_path);
+    let result = if delta {
+        crate::baseline::create_delta_baseline(
+            &baseline.scan_path, &baseline.remap_to, data_dir, baseline, ScanControl::new(), |_, _, _| {},
+        )
+    } else {
+        crate::baseline::create_initial_baseline(
+            &baseline.scan_path, &baseline.remap_to, data_dir, baseline, ScanControl::new(), |_, _, _| {},
+        )
+    };
+    
+    let created = match result {
+        Ok(created) => created,
+        Err(e) => {
+            eprintln!("chamon: baseline failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(label) = label {
+        if let Err(e) = Baseline::update_metadata(data_dir, &created.filename(), |b| b.label = Some(label)) {
+            eprintln!("chamon: baseline saved but labeling failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    
+    if delta {
+        println!(
+            "created delta {}: {} changed, {} removed",
+            created.version, created.file_count, created.removed.len(),
+        );
+    } else {
+        println!("created initial baseline {}: {} files", created.version, created.file_count);
+    }
+    ExitCode::SUCCESS
+}
+
+fn list(data_dir: &Path) -> ExitCode {
+    let index = match BaselineIndex::load_or_rebuild(data_dir) {
+        Ok(index) => index,
+        Err(e) => {
+            eprintln!("chamon: could not read baselines: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    
+    let mut entries: Vec<&IndexEntry> = index.entries.iter().collect();
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| b.version.cmp(&a.version)));
+    
+    println!("{:<17} {:<9} {:>8} {:>10}  {:<16}  LABEL", "VERSION", "TYPE", "FILES", "SIZE", "CREATED");
+    for entry in entries {
+        let mut label = entry.label.clone().unwrap_or_default();
+        if entry.pinned {
+            label.push_str(" [pinned]");
+        }
+        println!(
+            "{:<17} {:<9} {:>8} {:>10}  {:<16}  {}",
+            entry.version,
+            entry_type(entry),
+            entry.file_count,
+            format_size(entry.size_bytes),
+            format_time(entry.created_at),
+            label.trim(),
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn delete(data_dir: &Path, name: &str, merge: bool) -> ExitCode {
+    let entry = match resolve(data_dir, name) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    if entry.pinned {
+        eprintln!("chamon: {} is pinned; unpin it before deleting", entry.version);
+        return ExitCode::FAILURE;
+    }
+    
+    let index = BaselineIndex::load_or_rebuild(data_dir).unwrap_or_default();
+    let result = match index.delete_impact(&entry.filename) {
+        DeleteImpact::ChainRoot { deltas } => {
+            eprintln!("chamon: {} deltas are built on the initial baseline; delete or prune them first", deltas);
+            return ExitCode::FAILURE;
+        }
+        DeleteImpact::HasSuccessor { version, .. } if !merge => {
+            eprintln!("chamon: {} depends on {}; pass --merge to fold it into {}", version, entry.version, version);
+            return ExitCode::FAILURE;
+        }
+        DeleteImpact::HasSuccessor { version, .. } => {
+            Baseline::merge_into_successor(data_dir, &entry.filename)
+                .map(|_| format!("merged {} into {} and deleted it", entry.version, version))
+        }
+        DeleteImpact::Standalone => {
+            Baseline::remove_file(data_dir, &entry.filename).map(|_| format!("deleted {}", entry.version))
+        }
+    };
+    
+    match result {
+        Ok(message) => {
+            println!("{}", message);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chamon: delete failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn export(data_dir: &Path, name: &str, output: Option<PathBuf>, flatten: bool) -> ExitCode {
+    let entry = match resolve(data_dir, name) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    if warn_if_tampered(data_dir, &[&entry.filename]) {
+        eprintln!("chamon: refusing to export a modified baseline");
+        return ExitCode::FAILURE;
+    }
+    
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("chamon-{}.tar.zst", entry.version)));
+    match crate::archive::export(data_dir, &entry.filename, &output, flatten) {
+        Ok(manifest) => {
+            if flatten {
+                println!("exported {} as a standalone baseline to {}", manifest.target, output.display());
+            } else {
+                println!(
+                    "exported {} baselines (up to {}) to {}",
+                    manifest.baselines.len(), manifest.target, output.display(),
+                );
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chamon: export failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Baseline kind as shown by `baseline list` and `baseline show`
+fn entry_type(entry: &IndexEntry) -> &'static str {
+    if entry.reference {
+        "reference"
+    } else if entry.is_delta {
+        "delta"
+    } else {
+        "initial"
+    }
+}
+
+fn import(data_dir: &Path, archive: &Path, dry_run: bool) -> ExitCode {
+    let report = match crate::archive::import(data_dir, archive, dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("chamon: import failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(manifest) = &report.manifest {
+        println!(
+            "{}: {} baselines from {}, exported {}",
+            archive.display(), manifest.baselines.len(), manifest.host, manifest.created_at,
+        );
+    }
+    
+    if !report.conflicts.is_empty() {
+        for conflict in &report.conflicts {
+            eprintln!("chamon: conflict: {}", conflict);
+        }
+        eprintln!("chamon: nothing imported; use --into to import into a separate data dir");
+        return ExitCode::FAILURE;
+    }
+    
+    let verb = if dry_run { "would import" } else { "imported" };
+    for version in &report.imported {
+        match &report.reference {
+            Some(filename) => println!("{} {} as the reference baseline {}", verb, version, filename),
+            None => println!("{} {}", verb, version),
+        }
+    }
+    for version in &report.skipped {
+        println!("already present: {}", version);
+    }
+    println!("{} {} baselines into {}", verb, report.imported.len(), data_dir.display());
+    ExitCode::SUCCESS
+}
+
+/// Exits non-zero when any baseline was modified; unsealed and unsigned
+/// baselines are only reported
+fn verify(data_dir: &Path) -> ExitCode {
+    let issues = match crate::integrity::verify_all(data_dir) {
+        Ok(issues) => issues,
+        Err(e) => {
+            eprintln!("chamon: could not read baselines: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    
+    let mut tampered = 0;
+    for issue in &issues {
+        if issue.problem.is_tampering() {
+            tampered += 1;
+            println!("FAIL {}", issue);
+        } else {
+            println!("note {}", issue);
+        }
+    }
+    
+    if tampered > 0 {
+        println!("{} integrity failures; these baselines changed after they were taken", tampered);
+        ExitCode::FAILURE
+    } else {
+        println!("baselines verified");
+        ExitCode::SUCCESS
+    }
+}
+
+fn keygen(force: bool) -> ExitCode {
+    match crate::integrity::generate_key(force) {
+        Ok(path) => {
+            println!("wrote {} and its public key", path.display());
+            println!("new baselines will be signed; keep a copy of the .pub file off this machine");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chamon: keygen failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show(data_dir: &Path, name: &str, files: bool) -> ExitCode {
+    let entry = match resolve(data_dir, name) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    
+    let baseline = match Baseline::load(data_dir, &entry.filename) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("chamon: could not read {}: {}", entry.filename, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    
+    println!("version:   {}", entry.version);
+    println!("file:      {}", entry.filename);
+    println!("type:      {}", entry_type(&entry));
+    if let Some(parent) = &entry.parent {
+        println!("parent:    {}", parent);
+    }
+    println!("created:   {}", format_time(entry.created_at));
+    println!("host:      {}", entry.host);
+    println!("scanned:   {} (as {})", entry.scan_path, entry.remap_to);
+    println!("files:     {}", entry.file_count);
+    println!("size:      {}", format_size(entry.size_bytes));
+    println!("label:     {}", entry.label.as_deref().unwrap_or("-"));
+    println!("pinned:    {}", if entry.pinned { "yes" } else { "no" });
+    println!("digest:    {}", baseline.digest.as_deref().unwrap_or("-"));
+    println!("signed:    {}", if baseline.signature.is_some() { "yes" } else { "no" });
+    if let Some(notes) = &entry.notes {
+        println!("notes:");
+        for line in notes.lines() {
+            println!("  {}", line);
+        }
+    }
+    
+    if files {
+        let mut paths: Vec<&String> = baseline.files.keys().collect();
+        paths.sort();
+        for path in paths {
+            println!("{}", path);
+        }
+        for path in &baseline.removed {
+            println!("{} (removed)", path);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn diff(config: &Config, data_dir: &Path, from: &str, to: &str, report: &ReportArgs) -> ExitCode {
+    let (from, to) = match (resolve(data_dir, from), resolve(data_dir, to)) {
+        (Ok(from), Ok(to)) => (from, to),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+    
+    warn_if_tampered(data_dir, &[&from.filename, &to.filename]);
+    match crate::compare::compare_baselines(data_dir, &from.filename, &to.filename) {
+        Ok(comparison) => output_comparison(config, &comparison, report),
+        Err(e) => {
+            eprintln!("chamon: diff failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The baseline to compare the live system against: the one given, else the
+/// one chosen as active in the TUI, else the latest
+fn live_target(data_dir: &Path, against: Option<&str>) -> Result<IndexEntry, ExitCode> {
+    let name = against.map(str::to_string)
+        .or_else(|| crate::state::AppState::load(data_dir).active_baseline)
+        .unwrap_or_else(|| "latest".to_string());
+    resolve(data_dir, &name)
+}
+
+fn status(config: &Config, data_dir: &Path, against: Option<&str>, report: &ReportArgs) -> ExitCode {
+    let entry = match live_target(data_dir, against) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    
+    warn_if_tampered(data_dir, &[&entry.filename]);
+    eprintln!("scanning {} ...", config.baseline.scan_path);
+    match crate::compare::compare_to_live(data_dir, &entry.filename, &config.baseline, ScanControl::new()) {
+        Ok(comparison) => output_comparison(config, &comparison, report),
+        Err(e) => {
+            eprintln!("chamon: status failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn check(config: &Config, data_dir: &Path, against: Option<&str>, quiet: bool) -> ExitCode {
+    let log_file = config.check_log_file();
+    // Failures are logged too, so a silent timer still leaves a trace
+    let fail = |message: String| {
+        eprintln!("chamon: {}", message);
+        let line = format!("{} error: {}", chrono::Local::now().to_rfc3339(), message);
+        if let Err(e) = append_line(&log_file, &line) {
+            eprintln!("chamon: could not write {}: {}", log_file.display(), e);
+        }
+        ExitCode::from(CHECK_ERROR)
+    };
+
+    let entry = match live_target(data_dir, against) {
+        Ok(entry) => entry,
+        Err(_) => return fail(format!("no baseline to check against ({})", against.unwrap_or("latest"))),
+    };
+    // A modified baseline could hide the very drift being checked for
+    match crate::integrity::verify_chain(data_dir, &entry.filename) {
+        Ok(issues) => {
+            if let Some(issue) = issues.iter().find(|i| i.problem.is_tampering()) {
+                return fail(format!("integrity check failed: {}", issue));
+            }
+        }
+        Err(e) => return fail(format!("integrity check failed: {}", e)),
+    }
+    let comparison = match crate::compare::compare_to_live(data_dir, &entry.filename, &config.baseline, ScanControl::new()) {
+        Ok(comparison) => comparison,
+        Err(e) => return fail(format!("check failed: {}", e)),
+    };
+    let matches = match crate::drift::evaluate(&comparison, &config.check.rules) {
+        Ok(matches) => matches,
+        Err(e) => return fail(format!("invalid drift rule: {}", e)),
+    };
+    let report = crate::drift::CheckReport::new(&comparison, matches);
+
+    if let Err(e) = append_line(&log_file, &report.log_line()) {
+        return fail(format!("could not write {}: {}", log_file.display(), e));
+    }
+    // Feeds the TUI's Timeline; the check result stands without it
+    let keep_days = config.retention.keep_journal_days;
+    if let Err(e) = crate::journal::Journal::default().record(data_dir, &comparison, keep_days) {
+        eprintln!("chamon: could not update the change journal: {}", e);
+    }
+    // A broker outage shouldn't hide the drift result, so this only warns
+    if config.mqtt.enabled {
+        if let Err(e) = crate::mqtt::publish_check(&config.mqtt, &report, &comparison) {
+            eprintln!("chamon: could not publish to {}: {}", config.mqtt.host, e);
+        }
+    }
+
+    if !quiet {
+        for rule in &report.rules {
+            println!("drift: {} ({} files)", rule.rule, rule.paths.len());
+            for path in &rule.paths {
+                println!("  {}", path);
+            }
+        }
+        println!(
+            "{} -> live: {} added, {} modified, {} removed; {}",
+            report.against,
+            report.added,
+            report.modified,
+            report.removed,
+            if report.drift { "drift detected" } else { "no drift" },
+        );
+    }
+
+    if report.drift {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+fn restore(data_dir: &Path, name: &str, paths: &[String], dry_run: bool) -> ExitCode {
+    let entry = match resolve(data_dir, name) {
+        Ok(entry) => entry,
+        Err(code) => return code,
+    };
+    
+    if warn_if_tampered(data_dir, &[&entry.filename]) && !dry_run {
+        eprintln!("chamon: refusing to restore from a modified baseline");
+        return ExitCode::FAILURE;
+    }
+    let items = match crate::restore::restore(data_dir, &entry.filename, paths, dry_run) {
+        Ok(items) => items,
+        Err(e) => {
+            eprintln!("chamon: restore failed: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    
+    let mut failed = false;
+    for item in &items {
+        match &item.action {
+            RestoreAction::Content if dry_run => println!("would restore {}", item.path),
+            RestoreAction::Content => println!("restored {}", item.path),
+            RestoreAction::Metadata if dry_run => println!("would reset mode/owner of {}", item.path),
+            RestoreAction::Metadata => println!("reset mode/owner of {}", item.path),
+            RestoreAction::NotTracked => println!("cannot restore {} (content not tracked)", item.path),
+            RestoreAction::Failed(e) => {
+                failed = true;
+                eprintln!("chamon: could not restore {}: {}", item.path, e);
+            }
+        }
+    }
+    if items.is_empty() {
+        println!("nothing to restore; files match {}", entry.version);
+    }
+    
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Print the change list or write a report, as `report` asks
+fn output_comparison(config: &Config, comparison: &Comparison, report: &ReportArgs) -> ExitCode {
+    let text = match report.format {
+        Some(format) => {
+            // Ownership comes from the dpkg database of the scanned system
+            let packages = PackageDb::load(Path::new(&config.baseline.scan_path));
+            crate::report::render(comparison, &packages, format)
+        }
+        None => comparison_text(comparison),
+    };
+    
+    match &report.output {
+        Some(path) => match crate::baseline::write_atomic(path, text.as_bytes()) {
+            Ok(()) => {
+                eprintln!("wrote {}", path.display());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("chamon: could not write {}: {}", path.display(), e);
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            print!("{}", text);
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// One line per change, with its security findings indented below it
+fn comparison_text(comparison: &Comparison) -> String {
+    let mut text = String::new();
+    let mut by_severity = [0usize; 3];
+    for change in &comparison.changes {
+        text.push_str(&format!("{} {}\n", change.kind.symbol(), change.path));
+        let findings = crate::security::assess(change);
+        for finding in &findings {
+            text.push_str(&format!("    ! {}: {}\n", finding.severity.label(), finding.message));
+        }
+        if let Some(severity) = crate::security::max_severity(&findings) {
+            by_severity[severity as usize] += 1;
+        }
+    }
+    text.push_str(&format!(
+        "{} -> {}: {} added, {} modified, {} removed\n",
+        comparison.from,
+        comparison.to,
+        comparison.count(ChangeKind::Added),
+        comparison.count(ChangeKind::Modified),
+        comparison.count(ChangeKind::Removed),
+    ));
+    let [low, medium, high] = by_severity;
+    if low + medium + high > 0 {
+        text.push_str(&format!("security: {} high, {} medium, {} low\n", high, medium, low));
+    }
+    text
+}
+
+fn format_time(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn prune(config: &Config, data_dir: &Path, dry_run: bool) -> ExitCode {
+    match crate::retention::prune(data_dir, &config.retention, dry_run) {
+        Ok(plan) => {
+            let verb = if dry_run { "would prune" } else { "pruned" };
+            for entry in plan.prune.iter().rev() {
+                println!("{} {}", verb, entry.version);
+            }
+            println!("{} {} deltas, kept {}", verb, plan.prune.len(), plan.keep.len());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chamon: prune failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Exercise the broker connection the way `chamon check` uses it, whether or
+/// not publishing is enabled
+fn mqtt_test(config: &Config) -> ExitCode {
+    let mqtt = &config.mqtt;
+    let credentials = crate::mqtt::Credentials::load(&mqtt.secrets_path());
+    let topics = crate::mqtt::Topics::new(mqtt);
+    println!(
+        "broker {}:{} as {}",
+        mqtt.host,
+        mqtt.port,
+        credentials.username.as_deref().unwrap_or("anonymous"),
+    );
+    if credentials.password.is_none() {
+        println!("no MQTT_PASSWORD in {}", mqtt.secrets_path().display());
+    }
+    
+    let payload = serde_json::json!({ "test": true, "at": chrono::Local::now().to_rfc3339() }).to_string();
+    let result = crate::mqtt::publish(mqtt, &credentials, &topics.test, &payload, false)
+        .map(|()| println!("published to {}", topics.test))
+        .and_then(|()| {
+            if mqtt.discovery {
+                crate::mqtt::publish_discovery(mqtt, &credentials, &topics)?;
+                println!("published discovery configs under {}/", mqtt.discovery_prefix);
+            }
+            Ok(())
+        });
+    
+    match result {
+        Ok(()) => {
+            println!("check results go to {} and {}", topics.drift, topics.changes);
+            if !mqtt.enabled {
+                println!("note: mqtt.enabled is false, so `chamon check` won't publish");
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("chamon: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Print every diagnostic; exit non-zero only when there are errors
+fn config_check(loaded: &LoadedConfig) -> ExitCode {
+    for source in &loaded.sources {
+        println!("using {}", source.path.display());
+    }
+    
+    let diagnostics = crate::validate::validate(loaded);
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+    
+    if crate::validate::has_errors(&diagnostics) {
+        ExitCode::FAILURE
+    } else {
+        if diagnostics.is_empty() {
+            println!("config ok");
+        }
+        ExitCode::SUCCESS
+    }
+}
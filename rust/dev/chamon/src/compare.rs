@@ -0,0 +1,152 @@
+// Comparing baselines: rebuilding a baseline's full file state and diffing states
+use crate::baseline::{scan_filesystem, Baseline, FileEntry, ScanControl};
+use crate::config::BaselineConfig;
+use crate::index::BaselineIndex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+/// Every file recorded at some point in the chain, keyed by path
+pub type FileState = HashMap<String, FileEntry>;
+
+/// Rebuild the full state a baseline describes by applying the chain from the
+/// initial baseline up to it: each delta drops its `removed` paths and then
+/// overlays its files.
+pub fn materialize(data_dir: &Path, index: &BaselineIndex, filename: &str) -> io::Result<FileState> {
+    let mut state = FileState::new();
+    for entry in index.chain(filename)? {
+        let baseline = Baseline::load(data_dir, &entry.filename)?;
+        for path in &baseline.removed {
+            state.remove(path);
+        }
+        state.extend(baseline.files);
+    }
+    Ok(state)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    /// One-letter marker used in listings, as in `git status --short`
+    pub fn symbol(&self) -> char {
+        match self {
+            ChangeKind::Added => 'A',
+            ChangeKind::Removed => 'D',
+            ChangeKind::Modified => 'M',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    /// The file in the older state (None when added)
+    pub old: Option<FileEntry>,
+    /// The file in the newer state (None when removed)
+    pub new: Option<FileEntry>,
+}
+
+impl FileChange {
+    /// A modification that only touched mode or ownership
+    pub fn is_metadata_only(&self) -> bool {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => old.track_mode.same_data(&new.track_mode),
+            _ => false,
+        }
+    }
+}
+
+/// Differences between two states, `from` being the older one
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub from: String,
+    pub to: String,
+    pub changes: Vec<FileChange>,
+}
+
+impl Comparison {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// Files added, removed or modified going from `old` to `new`, sorted by path
+pub fn diff_states(old: &FileState, new: &FileState) -> Vec<FileChange> {
+    let mut changes = Vec::new();
+
+    for (path, entry) in new {
+        match old.get(path) {
+            None => changes.push(FileChange {
+                path: path.clone(),
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(entry.clone()),
+            }),
+            Some(previous) if !previous.track_mode.same_as(&entry.track_mode) => changes.push(FileChange {
+                path: path.clone(),
+                kind: ChangeKind::Modified,
+                old: Some(previous.clone()),
+                new: Some(entry.clone()),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for (path, entry) in old {
+        if !new.contains_key(path) {
+            changes.push(FileChange {
+                path: path.clone(),
+                kind: ChangeKind::Removed,
+                old: Some(entry.clone()),
+                new: None,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+/// Compare two stored baselines by file name
+pub fn compare_baselines(data_dir: &Path, from: &str, to: &str) -> io::Result<Comparison> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let version = |filename: &str| {
+        index.get(filename).map(|e| e.version.clone()).unwrap_or_else(|| filename.to_string())
+    };
+
+    let old = materialize(data_dir, &index, from)?;
+    let new = materialize(data_dir, &index, to)?;
+
+    Ok(Comparison {
+        from: version(from),
+        to: version(to),
+        changes: diff_states(&old, &new),
+    })
+}
+
+/// Compare a stored baseline against a fresh scan of the filesystem, using
+/// the configured scan root and rules
+pub fn compare_to_live(
+    data_dir: &Path,
+    filename: &str,
+    config: &BaselineConfig,
+    control: ScanControl,
+) -> io::Result<Comparison> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let old = materialize(data_dir, &index, filename)?;
+    let scan = scan_filesystem(&config.scan_path, &config.remap_to, config, control, |_, _, _| {})?;
+
+    Ok(Comparison {
+        from: index.get(filename).map(|e| e.version.clone()).unwrap_or_else(|| filename.to_string()),
+        to: "live".to_string(),
+        changes: diff_states(&old, &scan.files),
+    })
+}
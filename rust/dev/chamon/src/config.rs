@@ -1,24 +1,468 @@
 // Chamon configuration
+use crate::compare::ChangeKind;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// System-wide configuration, overridden by the user's
+pub const SYSTEM_CONFIG_PATH: &str = "/etc/chamon/config.yaml";
+
+/// Where baselines were kept before `data_dir` became configurable
+pub const LEGACY_DATA_DIR: &str = "/root/_playground/rust/dev/chamon/data";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub title: String,
-    #[serde(default)]
+    /// Where baselines and session state are stored; see `Config::data_dir`
+    pub data_dir: Option<PathBuf>,
     pub baseline: BaselineConfig,
+    pub retention: RetentionConfig,
+    pub check: CheckConfig,
+    pub mqtt: MqttConfig,
+    pub dashboard: DashboardConfig,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             title: "CHAMON - File change Monitor".to_string(),
+            data_dir: None,
             baseline: BaselineConfig::default(),
+            retention: RetentionConfig::default(),
+            check: CheckConfig::default(),
+            mqtt: MqttConfig::default(),
+            dashboard: DashboardConfig::default(),
+        }
+    }
+}
+
+/// A config file that contributed to the loaded configuration
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// A problem found while loading the configuration. `source` is a file path
+/// or the name of an environment variable.
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    pub source: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.source, line, self.message),
+            None => write!(f, "{}: {}", self.source, self.message),
+        }
+    }
+}
+
+/// The merged configuration plus where it came from. A layer that fails to
+/// read or parse is skipped and reported in `errors`, so the remaining
+/// layers still apply. `warnings` are problems that don't stop chamon from
+/// running.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub config: Config,
+    pub sources: Vec<ConfigSource>,
+    pub errors: Vec<ConfigError>,
+    pub warnings: Vec<ConfigError>,
+}
+
+impl Config {
+    /// Load the layered configuration: built-in defaults, then
+    /// `/etc/chamon/config.yaml`, then the user's config (`explicit` if given,
+    /// otherwise `$XDG_CONFIG_HOME/chamon/config.yaml`), then environment
+    /// overrides. Mappings merge key by key; lists replace the lower layer's.
+    pub fn load(explicit: Option<&Path>) -> LoadedConfig {
+        let mut merged = serde_yaml::to_value(Config::default()).unwrap_or(Value::Null);
+        let mut sources = Vec::new();
+        let mut errors = Vec::new();
+        
+        for (path, required) in Self::search_paths(explicit) {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == io::ErrorKind::NotFound && !required => continue,
+                Err(e) => {
+                    errors.push(ConfigError {
+                        source: path.display().to_string(),
+                        line: None,
+                        message: e.to_string(),
+                    });
+                    continue;
+                }
+            };
+            
+            match parse_layer(&content) {
+                Ok(layer) => {
+                    merge_values(&mut merged, layer);
+                    sources.push(ConfigSource { path, content });
+                }
+                Err(e) => errors.push(ConfigError {
+                    source: path.display().to_string(),
+                    line: e.location().map(|l| l.line()),
+                    message: e.to_string(),
+                }),
+            }
+        }
+        
+        // Each layer was checked on its own, but report rather than hide a
+        // merged result that still doesn't fit
+        let mut config: Config = match serde_yaml::from_value(merged) {
+            Ok(config) => config,
+            Err(e) => {
+                errors.push(ConfigError {
+                    source: "merged configuration".to_string(),
+                    line: None,
+                    message: format!("{} (using the built-in defaults)", e),
+                });
+                Config::default()
+            }
+        };
+        config.apply_env(&mut errors);
+        
+        let mut warnings = Vec::new();
+        config.fall_back_to_legacy_data_dir(&mut warnings);
+        
+        LoadedConfig { config, sources, errors, warnings }
+    }
+    
+    /// Baselines taken before `data_dir` existed live in `LEGACY_DATA_DIR`.
+    /// If nothing sets `data_dir` and only that location has baselines, keep
+    /// using it (so they don't vanish from view) and say how to move on.
+    fn fall_back_to_legacy_data_dir(&mut self, warnings: &mut Vec<ConfigError>) {
+        if self.data_dir.is_some() {
+            return;
+        }
+        let legacy = PathBuf::from(LEGACY_DATA_DIR);
+        let default = self.data_dir();
+        if default.join("baselines").exists() || !legacy.join("baselines").is_dir() {
+            return;
+        }
+        warnings.push(ConfigError {
+            source: "data_dir".to_string(),
+            line: None,
+            message: format!(
+                "baselines found in the old location {}; still using it. Move it to {} or set `data_dir` to keep it where it is",
+                legacy.display(),
+                default.display(),
+            ),
+        });
+        self.data_dir = Some(legacy);
+    }
+    
+    /// Config files in the order they are layered, with whether a missing
+    /// file is an error
+    pub fn search_paths(explicit: Option<&Path>) -> Vec<(PathBuf, bool)> {
+        let mut paths = vec![(PathBuf::from(SYSTEM_CONFIG_PATH), false)];
+        match explicit {
+            Some(path) => paths.push((path.to_path_buf(), true)),
+            None => {
+                if let Some(dir) = config_dir() {
+                    paths.push((dir.join("config.yaml"), false));
+                }
+            }
+        }
+        paths
+    }
+    
+    /// Environment overrides, applied over every file layer:
+    /// `CHAMON_DATA_DIR` and `CHAMON_CONTENT_SIZE_LIMIT`
+    fn apply_env(&mut self, errors: &mut Vec<ConfigError>) {
+        if let Some(dir) = std::env::var_os("CHAMON_DATA_DIR").filter(|d| !d.is_empty()) {
+            self.data_dir = Some(PathBuf::from(dir));
+        }
+        if let Ok(limit) = std::env::var("CHAMON_CONTENT_SIZE_LIMIT") {
+            match limit.trim().parse() {
+                Ok(limit) => self.baseline.content_size_limit = limit,
+                Err(_) => errors.push(ConfigError {
+                    source: "CHAMON_CONTENT_SIZE_LIMIT".to_string(),
+                    line: None,
+                    message: format!("expected a size in bytes, got {:?}", limit),
+                }),
+            }
+        }
+    }
+    
+    /// Where `chamon check` appends its summaries
+    pub fn check_log_file(&self) -> PathBuf {
+        match &self.check.log_file {
+            Some(path) => expand_home(path),
+            None => self.data_dir().join("check.log"),
+        }
+    }
+    
+    /// Directory holding baselines and other chamon data: the configured
+    /// `data_dir` (a leading `~/` is expanded), otherwise
+    /// `$XDG_DATA_HOME/chamon`
+    pub fn data_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.data_dir {
+            return expand_home(dir);
+        }
+        xdg_dir("XDG_DATA_HOME", ".local/share")
+            .map(|dir| dir.join("chamon"))
+            .unwrap_or_else(|| PathBuf::from("/var/lib/chamon"))
+    }
+}
+
+/// Parse one config file. Deserializing it as a `Config` first catches type
+/// errors (with their line) before the layer is merged.
+fn parse_layer(content: &str) -> Result<Value, serde_yaml::Error> {
+    serde_yaml::from_str::<Config>(content)?;
+    let value: Value = serde_yaml::from_str(content)?;
+    // An empty file parses as null and contributes nothing
+    Ok(if value.is_null() { Value::Mapping(Default::default()) } else { value })
+}
+
+/// Merge `overlay` into `base`: mappings merge recursively, anything else
+/// (including lists) replaces the base value
+fn merge_values(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").filter(|h| !h.is_empty()).map(PathBuf::from)
+}
+
+/// chamon's directory under `$XDG_CONFIG_HOME` (default `~/.config/chamon`)
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("chamon"))
+}
+
+/// An XDG base directory from `var`, falling back to `~/<fallback>`
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(fallback)))
+}
+
+/// Drift checks run by `chamon check`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckConfig {
+    /// Each check appends a summary line here; defaults to `check.log` in the data dir
+    #[serde(default)]
+    pub log_file: Option<PathBuf>,
+    /// A change only counts as drift if it matches one of these rules
+    #[serde(default = "default_drift_rules")]
+    pub rules: Vec<DriftRule>,
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            log_file: None,
+            rules: default_drift_rules(),
         }
     }
 }
 
+/// A change matches when it is under one of `paths` (a directory prefix or a
+/// glob; empty matches everything), is one of `changes` (empty means any)
+/// and, with `setuid`, newly has the setuid bit. `config check` rejects a
+/// rule that sets none of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftRule {
+    pub name: String,
+    #[serde(default)]
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub changes: Vec<ChangeKind>,
+    #[serde(default)]
+    pub setuid: bool,
+}
+
+fn default_drift_rules() -> Vec<DriftRule> {
+    vec![
+        DriftRule {
+            name: "ssh-config".to_string(),
+            paths: vec!["/etc/ssh".to_string()],
+            changes: Vec::new(),
+            setuid: false,
+        },
+        DriftRule {
+            name: "new-setuid".to_string(),
+            paths: Vec::new(),
+            changes: vec![ChangeKind::Added, ChangeKind::Modified],
+            setuid: true,
+        },
+    ]
+}
+
+/// Local MQTT broker that `chamon check` publishes results to (via
+/// `mosquitto_pub`). Credentials come from `MQTT_USERNAME` and
+/// `MQTT_PASSWORD` in the secrets file, like the mqtt helper scripts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MqttConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_mqtt_host")]
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    /// Topics are `<topic>/<hostname>/drift` and `<topic>/<hostname>/changes`
+    #[serde(default = "default_mqtt_topic")]
+    pub topic: String,
+    #[serde(default = "default_secrets_file")]
+    pub secrets_file: PathBuf,
+    /// Publish Home Assistant discovery configs for the drift sensors
+    #[serde(default = "default_true")]
+    pub discovery: bool,
+    #[serde(default = "default_discovery_prefix")]
+    pub discovery_prefix: String,
+    /// Publish one event per changed file
+    #[serde(default = "default_true")]
+    pub events: bool,
+}
+
+fn default_mqtt_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_topic() -> String {
+    "chamon".to_string()
+}
+
+fn default_secrets_file() -> PathBuf {
+    PathBuf::from("~/.secrets")
+}
+
+fn default_discovery_prefix() -> String {
+    "homeassistant".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_mqtt_host(),
+            port: default_mqtt_port(),
+            topic: default_mqtt_topic(),
+            secrets_file: default_secrets_file(),
+            discovery: true,
+            discovery_prefix: default_discovery_prefix(),
+            events: true,
+        }
+    }
+}
+
+impl MqttConfig {
+    /// The secrets file with a leading `~/` expanded
+    pub fn secrets_path(&self) -> PathBuf {
+        expand_home(&self.secrets_file)
+    }
+}
+
+/// How the Dashboard keeps its drift summary current
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardConfig {
+    /// Rescan the live system this often while the TUI runs; 0 only
+    /// refreshes on request
+    #[serde(default = "default_refresh_minutes")]
+    pub refresh_minutes: u64,
+}
+
+fn default_refresh_minutes() -> u64 {
+    0
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self { refresh_minutes: default_refresh_minutes() }
+    }
+}
+
+/// Which delta baselines survive pruning. A delta is kept if any rule keeps
+/// it; pinned baselines, the initial baseline and the newest delta are never
+/// pruned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Always keep this many of the newest deltas
+    #[serde(default = "default_keep_last")]
+    pub keep_last: usize,
+    /// Keep the newest delta of each day for this many days
+    #[serde(default = "default_keep_daily")]
+    pub keep_daily: u32,
+    /// Keep the newest delta of each week for this many weeks
+    #[serde(default = "default_keep_weekly")]
+    pub keep_weekly: u32,
+    /// Drop change journal entries after this many days, keeping each
+    /// file's newest one; 0 keeps the whole journal
+    #[serde(default = "default_keep_journal_days")]
+    pub keep_journal_days: u32,
+}
+
+fn default_keep_last() -> usize {
+    5
+}
+
+fn default_keep_daily() -> u32 {
+    7
+}
+
+fn default_keep_weekly() -> u32 {
+    4
+}
+
+fn default_keep_journal_days() -> u32 {
+    90
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            keep_last: default_keep_last(),
+            keep_daily: default_keep_daily(),
+            keep_weekly: default_keep_weekly(),
+            keep_journal_days: default_keep_journal_days(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BaselineConfig {
+    /// Root of the filesystem to scan (e.g. a mounted image's rootfs)
+    #[serde(default = "default_root")]
+    pub scan_path: String,
+    /// Path that `scan_path` corresponds to on the live system
+    #[serde(default = "default_root")]
+    pub remap_to: String,
     #[serde(default)]
     pub exclude_directories: Vec<String>,
     #[serde(default)]
@@ -31,6 +475,10 @@ pub struct BaselineConfig {
     pub exclusion_log: String,
 }
 
+fn default_root() -> String {
+    "/".to_string()
+}
+
 fn default_content_size_limit() -> u64 {
     102400 // 100KB
 }
@@ -42,6 +490,8 @@ fn default_exclusion_log() -> String {
 impl Default for BaselineConfig {
     fn default() -> Self {
         Self {
+            scan_path: default_root(),
+            remap_to: default_root(),
             exclude_directories: vec![
                 // Virtual filesystems (kernel interfaces)
                 "/dev".to_string(),
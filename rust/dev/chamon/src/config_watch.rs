@@ -0,0 +1,77 @@
+// Watches the config files so the TUI can reload them while running
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+/// Quiet period after the last file event before reloading, so a save that
+/// arrives as several writes is only parsed once it's complete
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the directories holding the config files rather than the files
+/// themselves: editors often save by replacing the file, and a config that
+/// doesn't exist yet should still be picked up once it's created.
+pub struct ConfigWatcher {
+    // Dropping the watcher stops the notifications
+    _watcher: RecommendedWatcher,
+    rx: mpsc::Receiver<()>,
+    last_event: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    pub fn new(paths: Vec<PathBuf>) -> notify::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        // notify reports absolute, resolved paths; match against the same
+        let paths: Vec<PathBuf> = paths.iter().filter_map(|p| absolute(p)).collect();
+        let watched = paths.clone();
+
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            if let Ok(event) = result {
+                let relevant = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) && event.paths.iter().any(|p| watched.contains(p));
+                if relevant {
+                    let _ = tx.send(());
+                }
+            }
+        })?;
+
+        let mut dirs: Vec<PathBuf> = paths.iter().filter_map(|p| p.parent().map(PathBuf::from)).collect();
+        dirs.sort();
+        dirs.dedup();
+        for dir in dirs.iter().filter(|d| d.is_dir()) {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self { _watcher: watcher, rx, last_event: None })
+    }
+
+    /// Whether the config changed and has been quiet for `DEBOUNCE`; call
+    /// this from the main loop. Returns true once per burst of events.
+    pub fn poll(&mut self) -> bool {
+        while self.rx.try_recv().is_ok() {
+            self.last_event = Some(SystemTime::now());
+        }
+        
+        let settled = self.last_event
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|elapsed| elapsed >= DEBOUNCE);
+        if settled {
+            self.last_event = None;
+        }
+        settled
+    }
+}
+
+/// `path` with its directory canonicalized; a relative path (even a bare
+/// file name, whose parent is "") is taken from the current directory. The
+/// file itself may not exist yet. None when the directory doesn't exist.
+fn absolute(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    dir.canonicalize().ok().map(|dir| dir.join(name))
+}
@@ -0,0 +1,226 @@
+// Drift rules: which changes `chamon check` treats as a failure
+use crate::compare::{ChangeKind, Comparison, FileChange};
+use crate::config::DriftRule;
+use crate::restore::is_under;
+use crate::security;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+
+/// A rule together with the changes it matched
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub paths: Vec<String>,
+}
+
+/// A rule with its path patterns compiled
+struct CompiledRule<'a> {
+    rule: &'a DriftRule,
+    /// Plain paths, matched as the path itself or anything under it
+    prefixes: Vec<&'a str>,
+    globs: GlobSet,
+}
+
+impl<'a> CompiledRule<'a> {
+    fn new(rule: &'a DriftRule) -> Result<Self, globset::Error> {
+        let mut prefixes = Vec::new();
+        let mut globs = GlobSetBuilder::new();
+        for pattern in &rule.paths {
+            if is_glob(pattern) {
+                // `*` stays within one path component; `**` crosses them
+                globs.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+            } else {
+                prefixes.push(pattern.as_str());
+            }
+        }
+        Ok(Self { rule, prefixes, globs: globs.build()? })
+    }
+
+    fn matches(&self, change: &FileChange) -> bool {
+        let path_matches = self.rule.paths.is_empty()
+            || self.prefixes.iter().any(|p| is_under(&change.path, p))
+            || self.globs.is_match(&change.path);
+        let kind_matches = self.rule.changes.is_empty() || self.rule.changes.contains(&change.kind);
+
+        path_matches && kind_matches && (!self.rule.setuid || gained_setuid(change))
+    }
+}
+
+/// Check a pattern so `config check` can report it before a run does
+pub fn compile_pattern(pattern: &str) -> Result<(), globset::Error> {
+    if is_glob(pattern) {
+        GlobBuilder::new(pattern).literal_separator(true).build()?;
+    }
+    Ok(())
+}
+
+/// Rules that match at least one change in `comparison`, in rule order
+pub fn evaluate(comparison: &Comparison, rules: &[DriftRule]) -> Result<Vec<RuleMatch>, globset::Error> {
+    let mut matches = Vec::new();
+    for rule in rules {
+        let compiled = CompiledRule::new(rule)?;
+        let paths: Vec<String> = comparison.changes.iter()
+            .filter(|change| compiled.matches(change))
+            .map(|change| change.path.clone())
+            .collect();
+        if !paths.is_empty() {
+            matches.push(RuleMatch { rule: rule.name.clone(), paths });
+        }
+    }
+    Ok(matches)
+}
+
+/// Whether the file has the setuid bit now and didn't before (or is new)
+fn gained_setuid(change: &FileChange) -> bool {
+    security::assess(change).iter().any(|f| f.message == security::SETUID_SET)
+}
+
+pub(crate) fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Outcome of one `chamon check` run, as logged and published
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub checked_at: String,
+    pub against: String,
+    pub added: usize,
+    pub modified: usize,
+    pub removed: usize,
+    pub drift: bool,
+    pub rules: Vec<RuleMatch>,
+}
+
+impl CheckReport {
+    pub fn new(comparison: &Comparison, rules: Vec<RuleMatch>) -> Self {
+        Self {
+            checked_at: chrono::Local::now().to_rfc3339(),
+            against: comparison.from.clone(),
+            added: comparison.count(ChangeKind::Added),
+            modified: comparison.count(ChangeKind::Modified),
+            removed: comparison.count(ChangeKind::Removed),
+            drift: !rules.is_empty(),
+            rules,
+        }
+    }
+
+    /// One line for the check log, e.g.
+    /// `2025-01-01T03:00:00+00:00 against=v1 added=2 modified=1 removed=0 drift=ssh-config:1`
+    pub fn log_line(&self) -> String {
+        let drift = if self.rules.is_empty() {
+            "none".to_string()
+        } else {
+            self.rules.iter()
+                .map(|m| format!("{}:{}", m.rule, m.paths.len()))
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        format!(
+            "{} against={} added={} modified={} removed={} drift={}",
+            self.checked_at, self.against, self.added, self.modified, self.removed, drift,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::{FileEntry, TrackMode};
+
+    fn rule(paths: &[&str], changes: &[ChangeKind], setuid: bool) -> DriftRule {
+        DriftRule {
+            name: "test".to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+            changes: changes.to_vec(),
+            setuid,
+        }
+    }
+
+    fn entry(path: &str, permissions: u32) -> FileEntry {
+        FileEntry {
+            path: path.to_string(),
+            track_mode: TrackMode::Existence { size: 0, modified: 0, permissions, owner: 0, group: 0 },
+        }
+    }
+
+    fn change(path: &str, kind: ChangeKind) -> FileChange {
+        change_mode(path, kind, 0o100644, 0o100644)
+    }
+
+    fn change_mode(path: &str, kind: ChangeKind, old: u32, new: u32) -> FileChange {
+        FileChange {
+            path: path.to_string(),
+            kind,
+            old: (kind != ChangeKind::Added).then(|| entry(path, old)),
+            new: (kind != ChangeKind::Removed).then(|| entry(path, new)),
+        }
+    }
+
+    fn matches(rule: &DriftRule, change: &FileChange) -> bool {
+        CompiledRule::new(rule).unwrap().matches(change)
+    }
+
+    #[test]
+    fn plain_paths_match_themselves_and_below() {
+        let ssh = rule(&["/etc/ssh"], &[], false);
+        assert!(matches(&ssh, &change("/etc/ssh", ChangeKind::Modified)));
+        assert!(matches(&ssh, &change("/etc/ssh/sshd_config", ChangeKind::Modified)));
+        assert!(!matches(&ssh, &change("/etc/sshd", ChangeKind::Modified)));
+        assert!(!matches(&ssh, &change("/etc/hosts", ChangeKind::Modified)));
+    }
+
+    #[test]
+    fn single_star_stays_within_a_component() {
+        let single = rule(&["/etc/*.conf"], &[], false);
+        assert!(matches(&single, &change("/etc/resolv.conf", ChangeKind::Modified)));
+        assert!(!matches(&single, &change("/etc/nginx/nginx.conf", ChangeKind::Modified)));
+
+        let double = rule(&["/etc/**/*.conf"], &[], false);
+        assert!(matches(&double, &change("/etc/nginx/conf.d/site.conf", ChangeKind::Modified)));
+    }
+
+    #[test]
+    fn change_kinds_filter_matches() {
+        let kinds = rule(&["/usr/bin"], &[ChangeKind::Added, ChangeKind::Removed], false);
+        assert!(matches(&kinds, &change("/usr/bin/tool", ChangeKind::Added)));
+        assert!(matches(&kinds, &change("/usr/bin/tool", ChangeKind::Removed)));
+        assert!(!matches(&kinds, &change("/usr/bin/tool", ChangeKind::Modified)));
+    }
+
+    #[test]
+    fn setuid_needs_the_bit_newly_set() {
+        let setuid = rule(&[], &[], true);
+        assert!(matches(&setuid, &change_mode("/usr/bin/tool", ChangeKind::Added, 0, 0o104755)));
+        assert!(matches(&setuid, &change_mode("/usr/bin/tool", ChangeKind::Modified, 0o100755, 0o104755)));
+        assert!(!matches(&setuid, &change_mode("/usr/bin/tool", ChangeKind::Modified, 0o104755, 0o104755)));
+        assert!(!matches(&setuid, &change_mode("/usr/bin/tool", ChangeKind::Modified, 0o104755, 0o100755)));
+    }
+
+    #[test]
+    fn evaluate_reports_matching_rules_in_order() {
+        let comparison = Comparison {
+            from: "v1".to_string(),
+            to: "live".to_string(),
+            changes: vec![change("/etc/ssh/sshd_config", ChangeKind::Modified), change("/etc/hosts", ChangeKind::Added)],
+        };
+        let mut ssh = rule(&["/etc/ssh"], &[], false);
+        ssh.name = "ssh".to_string();
+        let mut cron = rule(&["/etc/cron.d"], &[], false);
+        cron.name = "cron".to_string();
+        let mut added = rule(&["/etc"], &[ChangeKind::Added], false);
+        added.name = "added".to_string();
+
+        let matched = evaluate(&comparison, &[ssh, cron, added]).unwrap();
+        let matched: Vec<(&str, Vec<&str>)> = matched.iter()
+            .map(|m| (m.rule.as_str(), m.paths.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(matched, [("ssh", vec!["/etc/ssh/sshd_config"]), ("added", vec!["/etc/hosts"])]);
+    }
+
+    #[test]
+    fn invalid_globs_are_reported() {
+        assert!(compile_pattern("/etc/[ssh").is_err());
+        assert!(compile_pattern("/etc/ssh").is_ok());
+        assert!(CompiledRule::new(&rule(&["/etc/{a,b"], &[], false)).is_err());
+    }
+}
@@ -1,15 +1,256 @@
 // Chamon event handling
-use crate::app::App;
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use crate::app::{App, Hit, Pane};
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseEvent};
+use tui_components::{MouseAction, PopupType};
 
 pub fn handle_event(event: Event, app: &mut App) -> bool {
     match event {
         Event::Key(key_event) => handle_key(key_event, app),
+        Event::Mouse(mouse_event) => handle_mouse(mouse_event, app),
         _ => false,
     }
 }
 
+/// Clicks pick tabs, list rows and popup buttons; the wheel moves through
+/// the list or scrolls the text under the pointer. Looks events up in the
+/// regions the last frame recorded, so overlays and popups take clicks
+/// the same way they take keys.
+fn handle_mouse(mouse: MouseEvent, app: &mut App) -> bool {
+    let Some(action) = MouseAction::from_event(&mouse) else {
+        return false;
+    };
+    match (action, app.hits.hit(mouse.column, mouse.row)) {
+        (MouseAction::Click, Some(Hit::PopupButton(index))) => {
+            if let Some(popup) = &mut app.popup {
+                popup.select_button(index);
+            }
+            app.popup_confirm();
+        }
+        (MouseAction::Click, None) => {
+            // Messages close on a click anywhere
+            let message = matches!(
+                app.popup.as_ref().map(|p| &p.popup_type),
+                Some(PopupType::Error { .. } | PopupType::Info { .. })
+            );
+            if !message {
+                return false;
+            }
+            app.popup_cancel();
+        }
+        (MouseAction::Click, Some(Hit::Tab(view))) => app.set_view(view),
+        (MouseAction::Click, Some(Hit::Row(pane, index))) => click_row(pane, index, app),
+        (MouseAction::ScrollUp | MouseAction::ScrollDown, Some(Hit::Row(pane, _) | Hit::Pane(pane))) => {
+            scroll_pane(pane, action == MouseAction::ScrollDown, app);
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Select the clicked row; clicking the selected row again acts like Enter
+fn click_row(pane: Pane, index: usize, app: &mut App) {
+    match pane {
+        Pane::Baselines => {
+            if index != app.selected_baseline {
+                app.baseline_browser = None;
+                app.selected_baseline = index;
+            } else if app.baseline_browser.is_none() && !app.creating_baseline {
+                app.browse_selected_baseline();
+            }
+        }
+        Pane::BaselineFiles => {
+            let Some(browser) = &mut app.baseline_browser else {
+                return;
+            };
+            if index != browser.selected {
+                browser.selected = index;
+            } else {
+                app.browse_selected_baseline();
+            }
+        }
+        Pane::Changes => {
+            if index != app.selected_change {
+                app.selected_change = index;
+                app.change_detail_scroll = 0;
+            } else {
+                app.toggle_selected_dir();
+            }
+        }
+        Pane::Timeline => {
+            if index != app.selected_event {
+                app.selected_event = index;
+            } else {
+                app.open_selected_event();
+            }
+        }
+        Pane::SearchResults => {
+            let Some(search) = &mut app.search else {
+                return;
+            };
+            if index != search.selected {
+                search.selected = index;
+            } else {
+                app.open_search_hit();
+            }
+        }
+        Pane::HistorySteps => {
+            let Some(history) = &mut app.history else {
+                return;
+            };
+            if index != history.selected {
+                history.selected = index;
+                history.scroll = 0;
+            } else {
+                app.open_history_step();
+            }
+        }
+        Pane::BaselineViewer | Pane::ChangeDetails | Pane::HistoryDetails => {}
+    }
+}
+
+/// One wheel step over a pane: lists move their selection, text scrolls
+fn scroll_pane(pane: Pane, down: bool, app: &mut App) {
+    match pane {
+        // Moving through the baselines would leave the open file browser
+        // showing a different one
+        Pane::Baselines if app.baseline_browser.is_some() => {}
+        // The browser scrolls the file it shows, or moves through the tree
+        Pane::Baselines | Pane::BaselineFiles | Pane::BaselineViewer => {
+            if down {
+                app.move_baseline_down();
+            } else {
+                app.move_baseline_up();
+            }
+        }
+        Pane::Changes => {
+            if down {
+                app.move_change_down();
+            } else {
+                app.move_change_up();
+            }
+        }
+        Pane::ChangeDetails => app.scroll_change_detail(down),
+        Pane::Timeline => {
+            if down {
+                app.move_event_down();
+            } else {
+                app.move_event_up();
+            }
+        }
+        Pane::SearchResults => {
+            if let Some(search) = &mut app.search {
+                if down {
+                    search.move_down();
+                } else {
+                    search.move_up();
+                }
+            }
+        }
+        Pane::HistorySteps | Pane::HistoryDetails => {
+            if let Some(history) = &mut app.history {
+                match (pane, down) {
+                    (Pane::HistorySteps, true) => history.move_down(),
+                    (Pane::HistorySteps, false) => history.move_up(),
+                    _ => history.scroll(down),
+                }
+            }
+        }
+    }
+}
+
+/// Keys for an open input popup: text editing, Enter to save, Esc to cancel
+fn handle_input_key(key: KeyEvent, app: &mut App) -> bool {
+    match key.code {
+        KeyCode::Enter => app.popup_confirm(),
+        KeyCode::Esc => app.popup_cancel(),
+        KeyCode::Left => app.popup_move_left(),
+        KeyCode::Right => app.popup_move_right(),
+        code => {
+            if let Some(popup) = &mut app.popup {
+                match code {
+                    KeyCode::Backspace => popup.delete_char_before_cursor(),
+                    KeyCode::Delete => popup.delete_char_at_cursor(),
+                    KeyCode::Home => popup.move_cursor_home(),
+                    KeyCode::End => popup.move_cursor_end(),
+                    KeyCode::Char(c) => popup.insert_char(c),
+                    _ => return false,
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Keys for the search results overlay: move, jump to the result, close,
+/// or start another search
+fn handle_search_key(key: KeyEvent, app: &mut App) -> bool {
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            if let Some(search) = &mut app.search {
+                search.move_up();
+            }
+            true
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if let Some(search) = &mut app.search {
+                search.move_down();
+            }
+            true
+        }
+        KeyCode::Enter => {
+            app.open_search_hit();
+            true
+        }
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.search = None;
+            true
+        }
+        KeyCode::Char('/') => {
+            app.show_search_input(crate::search::SearchMode::Paths);
+            true
+        }
+        KeyCode::Char('?') => {
+            app.show_search_input(crate::search::SearchMode::Contents);
+            true
+        }
+        KeyCode::Char('h') => {
+            app.show_history();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Keys for the file history overlay
+fn handle_history_key(key: KeyEvent, app: &mut App) -> bool {
+    let Some(history) = &mut app.history else {
+        return false;
+    };
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => history.move_up(),
+        KeyCode::Down | KeyCode::Char('j') => history.move_down(),
+        KeyCode::Char('[') => history.scroll(false),
+        KeyCode::Char(']') => history.scroll(true),
+        KeyCode::Enter => app.open_history_step(),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') => app.history = None,
+        _ => return false,
+    }
+    true
+}
+
 fn handle_key(key: KeyEvent, app: &mut App) -> bool {
+    // Text input popups take every key so typing 'q' doesn't quit
+    if app.popup_is_input() {
+        return handle_input_key(key, app);
+    }
+    // Search results and file history stay on top until closed
+    if app.popup.is_none() && app.history.is_some() {
+        return handle_history_key(key, app);
+    }
+    if app.popup.is_none() && app.search.is_some() {
+        return handle_search_key(key, app);
+    }
+    
     match (key.modifiers, key.code) {
         // Quit
         (_, KeyCode::Char('q')) | (_, KeyCode::Char('Q')) => {
@@ -25,6 +266,12 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             if app.popup.is_some() {
                 app.popup_cancel();
                 true
+            } else if app.creating_baseline {
+                app.request_cancel_scan();
+                true
+            } else if app.baseline_browser.is_some() && app.current_view == crate::app::ViewMode::Baselines {
+                app.close_baseline_browser();
+                true
             } else {
                 app.should_quit = true;
                 true
@@ -43,7 +290,7 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             true
         }
 
-        // Direct tab selection (1, 2, 3)
+        // Direct tab selection (1, 2, 3, 4)
         (_, KeyCode::Char('1')) => {
             app.set_view(crate::app::ViewMode::Dashboard);
             true
@@ -56,6 +303,37 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             app.set_view(crate::app::ViewMode::Baselines);
             true
         }
+        (_, KeyCode::Char('4')) => {
+            app.set_view(crate::app::ViewMode::Timeline);
+            true
+        }
+
+        // Search (any view)
+        (_, KeyCode::Char('/')) => {
+            if app.popup.is_none() {
+                app.show_search_input(crate::search::SearchMode::Paths);
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('?')) => {
+            if app.popup.is_none() {
+                app.show_search_input(crate::search::SearchMode::Contents);
+                true
+            } else {
+                false
+            }
+        }
+
+        (_, KeyCode::Char('h')) => {
+            if app.popup.is_none() {
+                app.show_history();
+                true
+            } else {
+                false
+            }
+        }
 
         // Navigation within views
         (_, KeyCode::Up | KeyCode::Char('k')) => {
@@ -65,6 +343,12 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             } else if app.current_view == crate::app::ViewMode::Baselines {
                 app.move_baseline_up();
                 true
+            } else if app.current_view == crate::app::ViewMode::Changes {
+                app.move_change_up();
+                true
+            } else if app.current_view == crate::app::ViewMode::Timeline {
+                app.move_event_up();
+                true
             } else {
                 false
             }
@@ -76,6 +360,12 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             } else if app.current_view == crate::app::ViewMode::Baselines {
                 app.move_baseline_down();
                 true
+            } else if app.current_view == crate::app::ViewMode::Changes {
+                app.move_change_down();
+                true
+            } else if app.current_view == crate::app::ViewMode::Timeline {
+                app.move_event_down();
+                true
             } else {
                 false
             }
@@ -100,7 +390,7 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
         // Baselines view actions
         (_, KeyCode::Char('n')) => {
             if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
-                // TODO: Create new baseline
+                app.create_delta_baseline();
                 true
             } else {
                 false
@@ -116,7 +406,144 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
         }
         (_, KeyCode::Char('c')) => {
             if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
-                // TODO: Compare to active
+                app.compare_selected_to_active();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('m')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.toggle_selected_mark();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('a')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.set_selected_active();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('l')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.show_label_input();
+                true
+            } else if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.compare_live_to_active();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('e')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.show_notes_input();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('p')) => {
+            if app.popup.is_none() && app.creating_baseline {
+                app.toggle_scan_pause();
+                true
+            } else if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.toggle_selected_pin();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('r')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.show_prune_confirmation();
+                true
+            } else if app.popup.is_none() && app.current_view == crate::app::ViewMode::Dashboard {
+                app.refresh_dashboard();
+                true
+            } else if app.popup.is_none() && app.current_view == crate::app::ViewMode::Timeline {
+                app.refresh_timeline();
+                true
+            } else {
+                false
+            }
+        }
+        
+        // Changes view actions
+        (_, KeyCode::Char('x')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.export_comparison();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('v')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.toggle_change_tree();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('b')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.toggle_split_compare();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('t')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.cycle_type_filter();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('s')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.cycle_severity_filter();
+                true
+            } else if app.popup.is_none() && app.current_view == crate::app::ViewMode::Baselines {
+                app.compare_marked();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('g')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.show_path_filter_input();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('o')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.show_package_filter_input();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Backspace) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.clear_change_filters();
+                true
+            } else {
+                false
+            }
+        }
+        (_, KeyCode::Char('[')) | (_, KeyCode::Char(']')) => {
+            if app.popup.is_none() && app.current_view == crate::app::ViewMode::Changes {
+                app.scroll_change_detail(key.code == KeyCode::Char(']'));
                 true
             } else {
                 false
@@ -136,6 +563,15 @@ fn handle_key(key: KeyEvent, app: &mut App) -> bool {
             if app.popup.is_some() {
                 app.popup_confirm();
                 true
+            } else if app.current_view == crate::app::ViewMode::Changes {
+                app.toggle_selected_dir();
+                true
+            } else if app.current_view == crate::app::ViewMode::Timeline {
+                app.open_selected_event();
+                true
+            } else if app.current_view == crate::app::ViewMode::Baselines && !app.creating_baseline {
+                app.browse_selected_baseline();
+                true
             } else {
                 false
             }
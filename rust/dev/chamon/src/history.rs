@@ -0,0 +1,127 @@
+// Per-file history: every baseline in the chain where one path changed,
+// with what was seen around it to tell who or what changed it
+use crate::baseline::{Baseline, FileEntry};
+use crate::compare::{ChangeKind, FileChange};
+use crate::index::BaselineIndex;
+use crate::journal::JournalEntry;
+use crate::packages::PackageEvent;
+use std::io;
+use std::path::Path;
+
+/// One baseline where the file changed
+#[derive(Debug, Clone)]
+pub struct HistoryStep {
+    pub filename: String,
+    pub version: String,
+    pub created_at: u64,
+    /// The file in the previous baseline of the chain and in this one
+    pub change: FileChange,
+    /// Installs, upgrades and removals of the owning package between the
+    /// previous baseline and this one
+    pub packages: Vec<PackageEvent>,
+    /// When a live scan first saw the change, if one ran in between
+    pub first_seen: Option<u64>,
+}
+
+/// The history of one path, oldest step first, as shown by the history
+/// overlay
+#[derive(Debug, Clone)]
+pub struct FileHistory {
+    pub path: String,
+    /// Package owning the file, if any
+    pub owner: Option<String>,
+    pub steps: Vec<HistoryStep>,
+    /// Index into `steps`; starts on the newest step
+    pub selected: usize,
+    /// First line shown in the step's details
+    pub scroll: u16,
+}
+
+impl FileHistory {
+    pub fn selected_step(&self) -> Option<&HistoryStep> {
+        self.steps.get(self.selected)
+    }
+
+    pub fn move_up(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            self.scroll = 0;
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.steps.len() {
+            self.selected += 1;
+            self.scroll = 0;
+        }
+    }
+
+    pub fn scroll(&mut self, down: bool) {
+        self.scroll = if down { self.scroll.saturating_add(5) } else { self.scroll.saturating_sub(5) };
+    }
+}
+
+/// Walk the baseline chain from the initial baseline to the newest one and
+/// collect each step where `path` was added, modified or removed.
+/// `owner`, `packages` and `journal` attribute the steps; pass empty ones
+/// when unknown.
+pub fn file_history(
+    data_dir: &Path,
+    path: &str,
+    owner: Option<&str>,
+    packages: &[PackageEvent],
+    journal: &[JournalEntry],
+) -> io::Result<FileHistory> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let chain = match index.head() {
+        Some(head) => index.chain(&head.filename)?,
+        None => Vec::new(),
+    };
+
+    let mut steps = Vec::new();
+    let mut current: Option<FileEntry> = None;
+    let mut previous_at = 0;
+    for entry in chain {
+        let baseline = Baseline::load(data_dir, &entry.filename)?;
+        let next = match baseline.files.get(path) {
+            Some(file) => Some(file.clone()),
+            None if baseline.removed.iter().any(|p| p == path) => None,
+            // Deltas only record what changed
+            None => current.clone(),
+        };
+        let kind = match (&current, &next) {
+            (None, Some(_)) => Some(ChangeKind::Added),
+            (Some(_), None) => Some(ChangeKind::Removed),
+            (Some(old), Some(new)) if !old.track_mode.same_as(&new.track_mode) => Some(ChangeKind::Modified),
+            _ => None,
+        };
+
+        if let Some(kind) = kind {
+            let in_window = |at: u64| at > previous_at && at <= entry.created_at;
+            steps.push(HistoryStep {
+                filename: entry.filename.clone(),
+                version: entry.version.clone(),
+                created_at: entry.created_at,
+                change: FileChange { path: path.to_string(), kind, old: current.clone(), new: next.clone() },
+                packages: packages.iter()
+                    .filter(|event| Some(event.package.as_str()) == owner && in_window(event.at))
+                    .cloned()
+                    .collect(),
+                first_seen: journal.iter()
+                    .filter(|seen| seen.path == path && seen.kind == kind && in_window(seen.seen_at))
+                    .map(|seen| seen.seen_at)
+                    .min(),
+            });
+        }
+        current = next;
+        previous_at = entry.created_at;
+    }
+
+    Ok(FileHistory {
+        path: path.to_string(),
+        owner: owner.map(str::to_string),
+        selected: steps.len().saturating_sub(1),
+        steps,
+        scroll: 0,
+    })
+}
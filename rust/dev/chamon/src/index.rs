@@ -0,0 +1,338 @@
+// Baseline index (manifest) for fast listing and metadata
+use crate::baseline::{write_atomic, Baseline};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Index file name, stored alongside the baselines in `data/baselines`
+pub const INDEX_FILENAME: &str = "index.json";
+
+/// What deleting a baseline would do to the delta chain
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeleteImpact {
+    /// Nothing depends on it; the file can simply be removed
+    Standalone,
+    /// A later delta was taken against it: deleting must merge into that delta
+    HasSuccessor { filename: String, version: String },
+    /// The initial baseline while deltas still build on it
+    ChainRoot { deltas: usize },
+}
+
+/// Bump when the on-disk index layout changes; older indexes are rebuilt
+const INDEX_FORMAT: u32 = 1;
+
+/// Summary of a single baseline file, kept in the index so the Baselines
+/// view never has to parse the (potentially very large) baseline JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub version: String,
+    pub filename: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub parent: Option<String>,
+    pub is_delta: bool,
+    /// Imported from another machine; not part of the local chain
+    #[serde(default)]
+    pub reference: bool,
+    pub file_count: usize,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    pub scan_path: String,
+    pub remap_to: String,
+    #[serde(default)]
+    pub host: String,
+}
+
+impl IndexEntry {
+    pub fn from_baseline(baseline: &Baseline, size_bytes: u64) -> Self {
+        Self {
+            version: baseline.version.clone(),
+            filename: baseline.filename(),
+            created_at: baseline.created_at,
+            parent: baseline.parent.clone(),
+            is_delta: baseline.is_delta,
+            reference: baseline.reference,
+            file_count: baseline.file_count,
+            size_bytes,
+            label: baseline.label.clone(),
+            notes: baseline.notes.clone(),
+            pinned: baseline.pinned,
+            scan_path: baseline.scan_path.clone(),
+            remap_to: baseline.remap_to.clone(),
+            host: baseline.host.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineIndex {
+    pub format: u32,
+    pub entries: Vec<IndexEntry>,
+}
+
+impl Default for BaselineIndex {
+    fn default() -> Self {
+        Self {
+            format: INDEX_FORMAT,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl BaselineIndex {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("baselines").join(INDEX_FILENAME)
+    }
+
+    /// Read the index as-is, without checking it against the directory
+    pub fn load(data_dir: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(Self::path(data_dir))?;
+        let index: BaselineIndex = serde_json::from_str(&content)?;
+        if index.format != INDEX_FORMAT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported index format {}", index.format),
+            ));
+        }
+        Ok(index)
+    }
+
+    /// Load the index, falling back to a full rebuild when it is missing or
+    /// unreadable. An existing index is reconciled with the directory listing:
+    /// entries whose file disappeared are dropped and unindexed baseline files
+    /// are parsed and added, so only new files ever need a full parse.
+    pub fn load_or_rebuild(data_dir: &Path) -> io::Result<Self> {
+        let mut index = match Self::load(data_dir) {
+            Ok(index) => index,
+            Err(_) => {
+                let index = Self::rebuild(data_dir)?;
+                index.save(data_dir)?;
+                return Ok(index);
+            }
+        };
+
+        let on_disk = list_baseline_files(data_dir)?;
+        let mut changed = false;
+
+        let before = index.entries.len();
+        index.entries.retain(|e| on_disk.contains(&e.filename));
+        changed |= index.entries.len() != before;
+
+        let known: HashSet<String> = index.entries.iter().map(|e| e.filename.clone()).collect();
+        for filename in on_disk.iter().filter(|f| !known.contains(*f)) {
+            if let Some(entry) = read_entry(data_dir, filename) {
+                index.entries.push(entry);
+                changed = true;
+            }
+        }
+
+        if changed {
+            index.save(data_dir)?;
+        }
+        Ok(index)
+    }
+
+    /// Rebuild the index by parsing every baseline file in the directory
+    pub fn rebuild(data_dir: &Path) -> io::Result<Self> {
+        let mut index = Self::default();
+        for filename in list_baseline_files(data_dir)? {
+            if let Some(entry) = read_entry(data_dir, &filename) {
+                index.entries.push(entry);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Write the index atomically (temp file + rename)
+    pub fn save(&self, data_dir: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&Self::path(data_dir), json.as_bytes())
+    }
+
+    pub fn get(&self, filename: &str) -> Option<&IndexEntry> {
+        self.entries.iter().find(|e| e.filename == filename)
+    }
+
+    pub fn get_mut(&mut self, filename: &str) -> Option<&mut IndexEntry> {
+        self.entries.iter_mut().find(|e| e.filename == filename)
+    }
+
+    /// Insert or replace the entry for the same baseline file
+    pub fn upsert(&mut self, entry: IndexEntry) {
+        if let Some(existing) = self.get_mut(&entry.filename) {
+            *existing = entry;
+        } else {
+            self.entries.push(entry);
+        }
+    }
+
+    pub fn remove(&mut self, filename: &str) -> Option<IndexEntry> {
+        let pos = self.entries.iter().position(|e| e.filename == filename)?;
+        Some(self.entries.remove(pos))
+    }
+
+    /// Delta entries ordered oldest to newest
+    pub fn deltas_oldest_first(&self) -> Vec<&IndexEntry> {
+        let mut deltas: Vec<&IndexEntry> = self.entries.iter().filter(|e| e.is_delta).collect();
+        deltas.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.version.cmp(&b.version)));
+        deltas
+    }
+
+    /// The delta taken against `filename`'s baseline, if any. Deltas without
+    /// a recorded parent are chained by creation time.
+    pub fn successor(&self, filename: &str) -> Option<&IndexEntry> {
+        let entry = self.get(filename).filter(|e| !e.reference)?;
+        if let Some(child) = self.entries.iter()
+            .find(|e| e.is_delta && e.parent.as_deref() == Some(entry.version.as_str()))
+        {
+            return Some(child);
+        }
+
+        let deltas = self.deltas_oldest_first();
+        if !entry.is_delta {
+            return deltas.into_iter().find(|e| e.parent.is_none());
+        }
+        let pos = deltas.iter().position(|e| e.filename == filename)?;
+        deltas.get(pos + 1).copied().filter(|e| e.parent.is_none())
+    }
+
+    /// The delta `filename` was taken against: its recorded parent, or for
+    /// deltas without one the previous delta by creation time
+    pub fn predecessor(&self, filename: &str) -> Option<&IndexEntry> {
+        let entry = self.get(filename)?;
+        if !entry.is_delta {
+            return None;
+        }
+        if let Some(parent) = &entry.parent {
+            return self.entries.iter().find(|e| &e.version == parent);
+        }
+
+        let deltas = self.deltas_oldest_first();
+        let pos = deltas.iter().position(|e| e.filename == filename)?;
+        match pos {
+            0 => self.initial(),
+            _ => Some(deltas[pos - 1]),
+        }
+    }
+
+    /// The local chain's initial baseline (references are never it)
+    pub fn initial(&self) -> Option<&IndexEntry> {
+        self.entries.iter().find(|e| !e.is_delta && !e.reference)
+    }
+
+    /// Newest baseline in the chain; new deltas are taken against it
+    pub fn head(&self) -> Option<&IndexEntry> {
+        self.deltas_oldest_first().last().copied().or_else(|| self.initial())
+    }
+
+    /// Baselines from the initial one up to `filename`, in the order they
+    /// have to be applied to rebuild `filename`'s state
+    pub fn chain(&self, filename: &str) -> io::Result<Vec<&IndexEntry>> {
+        let mut chain = vec![self.get(filename).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("Unknown baseline {}", filename))
+        })?];
+
+        while let Some(last) = chain.last().filter(|e| e.is_delta) {
+            let previous = self.predecessor(&last.filename).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Broken chain: the baseline {} was taken against is missing", last.version),
+                )
+            })?;
+            // A cycle means the parent links are corrupt
+            if chain.len() > self.entries.len() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Baseline parents form a cycle"));
+            }
+            chain.push(previous);
+        }
+
+        chain.reverse();
+        Ok(chain)
+    }
+
+    /// Find a baseline by name as typed on the command line: `initial`,
+    /// `latest`, a version, a file name or a label
+    pub fn resolve(&self, name: &str) -> Option<&IndexEntry> {
+        match name {
+            "initial" => return self.initial(),
+            "latest" | "head" => return self.head(),
+            _ => {}
+        }
+        self.entries.iter()
+            .find(|e| e.version == name || e.filename == name)
+            .or_else(|| self.entries.iter().find(|e| e.label.as_deref() == Some(name)))
+    }
+
+    /// Check whether `filename` can be deleted without breaking the chain
+    pub fn delete_impact(&self, filename: &str) -> DeleteImpact {
+        let is_initial = self.get(filename).map(|e| !e.is_delta && !e.reference).unwrap_or(false);
+        if is_initial {
+            let deltas = self.entries.iter().filter(|e| e.is_delta).count();
+            if deltas > 0 {
+                return DeleteImpact::ChainRoot { deltas };
+            }
+            return DeleteImpact::Standalone;
+        }
+
+        match self.successor(filename) {
+            Some(next) => DeleteImpact::HasSuccessor {
+                filename: next.filename.clone(),
+                version: next.version.clone(),
+            },
+            None => DeleteImpact::Standalone,
+        }
+    }
+
+    /// Record a saved baseline in the on-disk index
+    pub fn record(data_dir: &Path, entry: IndexEntry) -> io::Result<()> {
+        let mut index = Self::load_or_rebuild(data_dir)?;
+        index.upsert(entry);
+        index.save(data_dir)
+    }
+
+    /// Drop a baseline from the on-disk index
+    pub fn forget(data_dir: &Path, filename: &str) -> io::Result<()> {
+        let mut index = Self::load_or_rebuild(data_dir)?;
+        if index.remove(filename).is_some() {
+            index.save(data_dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Names of all baseline files (`baseline-*.json`) in the baselines directory
+fn list_baseline_files(data_dir: &Path) -> io::Result<HashSet<String>> {
+    let baselines_dir = data_dir.join("baselines");
+    let mut files = HashSet::new();
+    if !baselines_dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(&baselines_dir)?.flatten() {
+        if let Some(filename) = entry.file_name().to_str() {
+            if filename.starts_with("baseline-") && filename.ends_with(".json") {
+                files.insert(filename.to_string());
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Parse a baseline file and summarize it; unreadable files are skipped
+fn read_entry(data_dir: &Path, filename: &str) -> Option<IndexEntry> {
+    let baseline = Baseline::load(data_dir, filename).ok()?;
+    let size_bytes = fs::metadata(data_dir.join("baselines").join(filename))
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let mut entry = IndexEntry::from_baseline(&baseline, size_bytes);
+    // Trust the name on disk over the one derived from the contents
+    entry.filename = filename.to_string();
+    Some(entry)
+}
@@ -0,0 +1,560 @@
+// Tamper evidence for stored baselines: a content digest per baseline that
+// also covers its parent's digest (a hash chain), optionally signed with a
+// local ed25519 key
+use crate::baseline::{Baseline, FileEntry};
+use crate::index::BaselineIndex;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the digested bytes; bump it if the digested fields change
+const DIGEST_DOMAIN: &[u8] = b"chamon-baseline-v1\n";
+
+/// What the digest covers: everything describing the recorded state and
+/// where it sits in the chain. Label, notes and pinned are left out, since
+/// they are edited after the fact.
+#[derive(Serialize)]
+struct Sealed<'a> {
+    version: &'a str,
+    created_at: u64,
+    host: &'a str,
+    scan_path: &'a str,
+    remap_to: &'a str,
+    is_delta: bool,
+    // Left out when false, so digests of local baselines are unchanged
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    reference: bool,
+    parent: Option<&'a str>,
+    parent_digest: Option<&'a str>,
+    files: BTreeMap<&'a str, &'a FileEntry>,
+    removed: &'a [String],
+}
+
+/// SHA-256 over the baseline's recorded state, as lowercase hex
+pub fn digest(baseline: &Baseline) -> String {
+    let sealed = Sealed {
+        version: &baseline.version,
+        created_at: baseline.created_at,
+        host: &baseline.host,
+        scan_path: &baseline.scan_path,
+        remap_to: &baseline.remap_to,
+        is_delta: baseline.is_delta,
+        reference: baseline.reference,
+        parent: baseline.parent.as_deref(),
+        parent_digest: baseline.parent_digest.as_deref(),
+        files: baseline.files.iter().map(|(path, entry)| (path.as_str(), entry)).collect(),
+        removed: &baseline.removed,
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(DIGEST_DOMAIN);
+    hasher.update(serde_json::to_vec(&sealed).unwrap_or_default());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Whether the baseline still matches its digest. One without a digest
+/// can't tell and counts as intact, unless it records a parent digest: only
+/// sealing writes that, so its own digest was removed.
+pub fn is_intact(baseline: &Baseline) -> bool {
+    match &baseline.digest {
+        Some(recorded) => digest(baseline) == *recorded,
+        None => baseline.parent_digest.is_none(),
+    }
+}
+
+/// Fail unless `baseline` is intact and the parent digest it records is
+/// still its parent's. New baselines are always sealed, so this also
+/// catches a digest removed from a baseline built on a sealed one.
+pub fn ensure_intact(data_dir: &Path, index: &BaselineIndex, baseline: &Baseline, action: &str) -> io::Result<()> {
+    let parent_digest = match baseline.parent.as_ref().and_then(|p| index.entries.iter().find(|e| &e.version == p)) {
+        Some(parent) => Baseline::load(data_dir, &parent.filename)?.digest,
+        // A missing parent is the chain's problem, not this baseline's
+        None => baseline.parent_digest.clone(),
+    };
+    if !is_intact(baseline) || parent_digest != baseline.parent_digest {
+        return Err(tampered(&baseline.version, action));
+    }
+    Ok(())
+}
+
+/// Link `baseline` to its parent's digest, digest it and, when a signing key
+/// exists, sign the digest. Call before saving a new or rewritten baseline.
+pub fn seal(baseline: &mut Baseline, data_dir: &Path) -> io::Result<()> {
+    baseline.parent_digest = match &baseline.parent {
+        Some(parent) => {
+            let index = BaselineIndex::load_or_rebuild(data_dir)?;
+            let entry = index.entries.iter().find(|e| &e.version == parent).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::NotFound, format!("Parent baseline {} not found", parent))
+            })?;
+            Baseline::load(data_dir, &entry.filename)?.digest
+        }
+        None => None,
+    };
+
+    let digest = digest(baseline);
+    baseline.signature = load_signing_key()?
+        .map(|key| hex::encode(key.sign(digest.as_bytes()).to_bytes()));
+    baseline.digest = Some(digest);
+    Ok(())
+}
+
+/// Make an imported baseline's signature local: sign it with the local key
+/// if there is one, otherwise keep the signature only if the local public key
+/// accepts it. The digest is left alone, so tampering still shows.
+pub fn adopt_signature(baseline: &mut Baseline) -> io::Result<()> {
+    let Some(digest) = baseline.digest.clone() else { return Ok(()) };
+    if let Some(key) = load_signing_key()? {
+        baseline.signature = Some(hex::encode(key.sign(digest.as_bytes()).to_bytes()));
+        return Ok(());
+    }
+    let accepted = match (load_verifying_key()?, &baseline.signature) {
+        (Some(key), Some(signature)) => signature_valid(&key, &digest, signature),
+        _ => false,
+    };
+    if !accepted {
+        baseline.signature = None;
+    }
+    Ok(())
+}
+
+/// Re-seal and save every baseline built on `version`. Needed after a
+/// baseline is rewritten (merged), since its digest changed. A descendant
+/// that no longer matches its own digest is refused rather than given a
+/// fresh one, which would hide the edit; its parent link was checked by
+/// `ensure_descendants_intact` before `version` changed.
+pub fn reseal_descendants(data_dir: &Path, version: &str) -> io::Result<()> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    for filename in descendants(&index, version) {
+        let mut baseline = Baseline::load(data_dir, &filename)?;
+        if !is_intact(&baseline) {
+            return Err(tampered(&baseline.version, "re-seal"));
+        }
+        seal(&mut baseline, data_dir)?;
+        baseline.save(data_dir)?;
+    }
+    Ok(())
+}
+
+/// Fail if any baseline built on `version` no longer matches its digest.
+/// Check before rewriting `version`, so a refusal leaves everything as it was.
+pub fn ensure_descendants_intact(data_dir: &Path, version: &str) -> io::Result<()> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    for filename in descendants(&index, version) {
+        ensure_intact(data_dir, &index, &Baseline::load(data_dir, &filename)?, "merge")?;
+    }
+    Ok(())
+}
+
+/// File names of every baseline built on `version`, each before the
+/// baselines built on it
+fn descendants(index: &BaselineIndex, version: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut pending = vec![version.to_string()];
+    while let Some(parent) = pending.pop() {
+        for entry in index.entries.iter().filter(|e| e.parent.as_deref() == Some(parent.as_str())) {
+            found.push(entry.filename.clone());
+            pending.push(entry.version.clone());
+        }
+    }
+    found
+}
+
+fn tampered(version: &str, action: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Baseline {} does not match its digest; refusing to {}", version, action),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// Created before baselines were sealed; nothing to check against
+    Unsealed,
+    /// No digest, although a signing key exists or it records a parent
+    /// digest, which only sealing writes
+    DigestRemoved,
+    /// Contents no longer match the recorded digest
+    DigestMismatch,
+    /// The recorded parent digest doesn't match the parent baseline
+    ChainBroken { parent: String },
+    /// A signature that doesn't verify with the local public key
+    BadSignature,
+    /// A public key is set up but the baseline carries no signature
+    Unsigned,
+    Unreadable(String),
+}
+
+impl Problem {
+    /// Whether this points at modification rather than an older baseline
+    pub fn is_tampering(&self) -> bool {
+        !matches!(self, Problem::Unsealed | Problem::Unsigned)
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Unsealed => write!(f, "no digest (created before integrity checks)"),
+            Problem::DigestRemoved => write!(f, "no digest although baselines here are sealed; it was removed"),
+            Problem::DigestMismatch => write!(f, "contents do not match its digest; it was modified"),
+            Problem::ChainBroken { parent } => write!(f, "parent digest does not match {}; the chain was altered", parent),
+            Problem::BadSignature => write!(f, "signature does not verify"),
+            Problem::Unsigned => write!(f, "not signed"),
+            Problem::Unreadable(e) => write!(f, "could not be read: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IntegrityIssue {
+    pub version: String,
+    pub filename: String,
+    pub problem: Problem,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "baseline {}: {}", self.version, self.problem)
+    }
+}
+
+/// Verify every stored baseline
+pub fn verify_all(data_dir: &Path) -> io::Result<Vec<IntegrityIssue>> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let filenames: Vec<String> = index.entries.iter().map(|e| e.filename.clone()).collect();
+    Ok(verify(data_dir, &index, &filenames))
+}
+
+/// Verify the chain from the initial baseline up to `filename`
+pub fn verify_chain(data_dir: &Path, filename: &str) -> io::Result<Vec<IntegrityIssue>> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let filenames: Vec<String> = index.chain(filename)?.iter().map(|e| e.filename.clone()).collect();
+    Ok(verify(data_dir, &index, &filenames))
+}
+
+fn verify(data_dir: &Path, index: &BaselineIndex, filenames: &[String]) -> Vec<IntegrityIssue> {
+    let public_key = load_verifying_key().ok().flatten();
+    let mut issues = Vec::new();
+    // (version, parent, recorded parent digest) of each delta, and every
+    // stored digest by version, for checking the links afterwards.
+    // Baselines are loaded one at a time; they hold file contents.
+    let mut links = Vec::new();
+    let mut digests: BTreeMap<String, Option<String>> = BTreeMap::new();
+
+    for filename in filenames {
+        let baseline = match Baseline::load(data_dir, filename) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                issues.push(IntegrityIssue {
+                    version: index.get(filename).map(|e| e.version.clone()).unwrap_or_else(|| filename.clone()),
+                    filename: filename.clone(),
+                    problem: Problem::Unreadable(e.to_string()),
+                });
+                continue;
+            }
+        };
+        let mut report = |problem| issues.push(IntegrityIssue {
+            version: baseline.version.clone(),
+            filename: filename.clone(),
+            problem,
+        });
+
+        digests.insert(baseline.version.clone(), baseline.digest.clone());
+        match &baseline.digest {
+            // Only believable for a baseline from before sealing, with no
+            // key around that would have signed it
+            None if public_key.is_some() || baseline.parent_digest.is_some() => report(Problem::DigestRemoved),
+            None => report(Problem::Unsealed),
+            Some(recorded) => {
+                if digest(&baseline) != *recorded {
+                    report(Problem::DigestMismatch);
+                }
+                if let Some(key) = &public_key {
+                    match &baseline.signature {
+                        Some(signature) if !signature_valid(key, recorded, signature) => report(Problem::BadSignature),
+                        Some(_) => {}
+                        None => report(Problem::Unsigned),
+                    }
+                }
+            }
+        }
+        if let Some(parent) = baseline.parent {
+            links.push((baseline.version, filename.clone(), parent, baseline.parent_digest));
+        }
+    }
+
+    for (version, filename, parent, recorded) in links {
+        let parent_digest = match digests.get(&parent) {
+            Some(digest) => digest.clone(),
+            // Parent outside the verified set
+            None => index.entries.iter()
+                .find(|e| e.version == parent)
+                .and_then(|e| Baseline::load(data_dir, &e.filename).ok())
+                .and_then(|b| b.digest),
+        };
+        // Sealing records the parent's digest, none for an unsealed parent,
+        // so any difference means one side was altered: a digest removed
+        // from the parent, or a sealed chain continued by an unsealed child
+        if parent_digest != recorded {
+            issues.push(IntegrityIssue { version, filename, problem: Problem::ChainBroken { parent } });
+        }
+    }
+    issues
+}
+
+fn signature_valid(key: &VerifyingKey, digest: &str, signature: &str) -> bool {
+    let Ok(bytes) = hex::decode(signature) else { return false };
+    let Ok(signature) = Signature::from_slice(&bytes) else { return false };
+    key.verify(digest.as_bytes(), &signature).is_ok()
+}
+
+/// Where the signing key lives: `$CHAMON_SIGNING_KEY`, otherwise
+/// `signing.key` in chamon's config directory. The public half sits next to
+/// it with a `.pub` extension.
+pub fn signing_key_path() -> Option<PathBuf> {
+    std::env::var_os("CHAMON_SIGNING_KEY")
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| crate::config::config_dir().map(|dir| dir.join("signing.key")))
+}
+
+fn public_key_path(signing_key: &Path) -> PathBuf {
+    signing_key.with_extension("pub")
+}
+
+/// The signing key, if one has been generated
+fn load_signing_key() -> io::Result<Option<SigningKey>> {
+    let Some(path) = signing_key_path() else { return Ok(None) };
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(SigningKey::from_bytes(&decode_key(&text, &path)?))),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The public key: the `.pub` file, or derived from the signing key when only
+/// that is present
+fn load_verifying_key() -> io::Result<Option<VerifyingKey>> {
+    let Some(path) = signing_key_path() else { return Ok(None) };
+    let public_path = public_key_path(&path);
+    match fs::read_to_string(&public_path) {
+        Ok(text) => VerifyingKey::from_bytes(&decode_key(&text, &public_path)?)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(load_signing_key()?.map(|k| k.verifying_key())),
+        Err(e) => Err(e),
+    }
+}
+
+fn decode_key(text: &str, path: &Path) -> io::Result<[u8; 32]> {
+    hex::decode(text.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not a hex-encoded ed25519 key", path.display()),
+        ))
+}
+
+/// Generate a signing key (readable only by its owner) and its public key.
+/// Returns the signing key's path. Refuses to replace an existing key unless
+/// `force` is set, since baselines signed with it would no longer verify.
+pub fn generate_key(force: bool) -> io::Result<PathBuf> {
+    let path = signing_key_path().ok_or_else(|| io::Error::new(
+        io::ErrorKind::NotFound,
+        "No config directory; set CHAMON_SIGNING_KEY",
+    ))?;
+    if path.exists() && !force {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+
+    let mut seed = [0u8; 32];
+    {
+        use std::io::Read;
+        fs::File::open("/dev/urandom")?.read_exact(&mut seed)?;
+    }
+    let key = SigningKey::from_bytes(&seed);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    {
+        use std::io::Write;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path)?;
+        writeln!(file, "{}", hex::encode(key.to_bytes()))?;
+    }
+    fs::write(public_key_path(&path), format!("{}\n", hex::encode(key.verifying_key().to_bytes())))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::baseline::TrackMode;
+
+    /// A fresh data dir under the system temp dir. Every test points the
+    /// signing key at the same missing file, so a developer's own key
+    /// doesn't sign or verify anything here.
+    fn data_dir(name: &str) -> PathBuf {
+        std::env::set_var("CHAMON_SIGNING_KEY", std::env::temp_dir().join("chamon-test-no-signing-key"));
+        let dir = std::env::temp_dir().join(format!("chamon-test-integrity-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn file(path: &str, content: &str) -> FileEntry {
+        FileEntry {
+            path: path.to_string(),
+            track_mode: TrackMode::Content {
+                checksum: format!("{:x}", Sha256::digest(content.as_bytes())),
+                content: content.to_string(),
+                size: content.len() as u64,
+                modified: 1_700_000_000,
+                permissions: 0o100644,
+                owner: 0,
+                group: 0,
+            },
+        }
+    }
+
+    fn baseline(version: &str, parent: Option<&Baseline>) -> Baseline {
+        let mut baseline = Baseline::new("/".to_string(), "/".to_string());
+        baseline.version = version.to_string();
+        if let Some(parent) = parent {
+            baseline.is_delta = true;
+            baseline.parent = Some(parent.version.clone());
+            baseline.created_at = parent.created_at + 60;
+        }
+        baseline
+    }
+
+    /// An initial baseline and a delta on it, both sealed and saved
+    fn sealed_chain(data_dir: &Path) -> (Baseline, Baseline) {
+        let mut initial = baseline("20250101-000000", None);
+        initial.add_file("/etc/hosts".to_string(), file("/etc/hosts", "127.0.0.1 localhost\n"));
+        seal(&mut initial, data_dir).unwrap();
+        initial.save(data_dir).unwrap();
+
+        let mut delta = baseline("20250102-000000", Some(&initial));
+        delta.add_file("/etc/hostname".to_string(), file("/etc/hostname", "box\n"));
+        seal(&mut delta, data_dir).unwrap();
+        delta.save(data_dir).unwrap();
+        (initial, delta)
+    }
+
+    /// Problems `verify_all` finds, by version
+    fn problems(data_dir: &Path) -> Vec<(String, Problem)> {
+        verify_all(data_dir).unwrap().into_iter().map(|i| (i.version, i.problem)).collect()
+    }
+
+    #[test]
+    fn sealed_chain_verifies() {
+        let dir = data_dir("sealed");
+        let (initial, delta) = sealed_chain(&dir);
+        assert!(is_intact(&initial) && is_intact(&delta));
+        assert_eq!(delta.parent_digest, initial.digest);
+        assert_eq!(problems(&dir), Vec::new());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn metadata_edits_keep_the_digest() {
+        let mut initial = baseline("20250101-000000", None);
+        initial.digest = Some(digest(&initial));
+        initial.label = Some("before upgrade".to_string());
+        initial.notes = Some("notes".to_string());
+        initial.pinned = true;
+        assert!(is_intact(&initial));
+    }
+
+    #[test]
+    fn edited_contents_fail_the_digest() {
+        let dir = data_dir("edited");
+        let (_, mut delta) = sealed_chain(&dir);
+        delta.add_file("/etc/hostname".to_string(), file("/etc/hostname", "other\n"));
+        assert!(!is_intact(&delta));
+
+        delta.save(&dir).unwrap();
+        assert_eq!(problems(&dir), vec![("20250102-000000".to_string(), Problem::DigestMismatch)]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unsealed_baseline_is_not_tampering() {
+        let dir = data_dir("unsealed");
+        let initial = baseline("20250101-000000", None);
+        assert!(is_intact(&initial));
+
+        initial.save(&dir).unwrap();
+        let problems = problems(&dir);
+        assert_eq!(problems, vec![("20250101-000000".to_string(), Problem::Unsealed)]);
+        assert!(!problems[0].1.is_tampering());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stripped_delta_digest_is_tampering() {
+        let dir = data_dir("stripped-delta");
+        let (_, mut delta) = sealed_chain(&dir);
+        delta.digest = None;
+        // It still records its parent's digest, which only sealing writes
+        assert!(!is_intact(&delta));
+
+        delta.save(&dir).unwrap();
+        let problems = problems(&dir);
+        assert_eq!(problems, vec![("20250102-000000".to_string(), Problem::DigestRemoved)]);
+        assert!(problems[0].1.is_tampering());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stripped_parent_digest_breaks_the_chain() {
+        let dir = data_dir("stripped-initial");
+        let (mut initial, delta) = sealed_chain(&dir);
+        initial.digest = None;
+        initial.save(&dir).unwrap();
+
+        // The initial baseline alone passes for one from before sealing
+        let problems = problems(&dir);
+        assert!(problems.contains(&("20250101-000000".to_string(), Problem::Unsealed)));
+        assert!(problems.contains(&(
+            "20250102-000000".to_string(),
+            Problem::ChainBroken { parent: "20250101-000000".to_string() },
+        )));
+        let index = BaselineIndex::load_or_rebuild(&dir).unwrap();
+        assert!(ensure_intact(&dir, &index, &delta, "merge").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stripping_a_delta_to_look_unsealed_breaks_the_chain() {
+        let dir = data_dir("stripped-both");
+        let (_, mut delta) = sealed_chain(&dir);
+        delta.digest = None;
+        delta.parent_digest = None;
+        // On its own it passes for a baseline from before sealing...
+        assert!(is_intact(&delta));
+
+        // ...but its parent is sealed
+        delta.save(&dir).unwrap();
+        let problems = problems(&dir);
+        assert!(problems.contains(&(
+            "20250102-000000".to_string(),
+            Problem::ChainBroken { parent: "20250101-000000".to_string() },
+        )));
+        let index = BaselineIndex::load_or_rebuild(&dir).unwrap();
+        assert!(ensure_intact(&dir, &index, &delta, "merge").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
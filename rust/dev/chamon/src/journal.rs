@@ -0,0 +1,186 @@
+// Change journal: file changes seen by live scans (Dashboard refreshes, live
+// compares and `chamon check`), kept so the Timeline can show when things
+// changed
+use crate::baseline::write_atomic;
+use crate::compare::{ChangeKind, Comparison};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Journal file name, stored directly in the data dir (one JSON entry per line)
+pub const JOURNAL_FILENAME: &str = "journal.jsonl";
+/// Lock file serializing writers (the TUI and `chamon check`). The journal
+/// itself can't carry the lock: compaction replaces it.
+const LOCK_FILENAME: &str = "journal.lock";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When a scan first saw the change (seconds since the epoch)
+    pub seen_at: u64,
+    pub path: String,
+    pub kind: ChangeKind,
+    /// The file's modification time as scanned; none for removed files
+    pub modified: Option<u64>,
+    /// Version of the baseline the change was measured against
+    pub against: String,
+}
+
+impl JournalEntry {
+    /// Best guess at when the change happened: the file's own modification
+    /// time when known, otherwise when it was noticed
+    pub fn happened_at(&self) -> u64 {
+        self.modified.unwrap_or(self.seen_at)
+    }
+}
+
+pub fn path(data_dir: &Path) -> PathBuf {
+    data_dir.join(JOURNAL_FILENAME)
+}
+
+/// The journal as read so far. `sync` only reads what was appended since
+/// the previous call, so the TUI doesn't reparse the whole file on every
+/// Dashboard or Timeline refresh.
+#[derive(Debug, Default)]
+pub struct Journal {
+    /// Oldest first
+    entries: Vec<JournalEntry>,
+    /// Position in `entries` of each path's newest entry
+    latest: HashMap<String, usize>,
+    /// Bytes of the file read so far
+    offset: u64,
+    /// Identity of the file read, so a compacted (replaced) journal is read
+    /// again from the start
+    file_id: Option<u64>,
+}
+
+impl Journal {
+    /// Read the whole journal. A missing journal is empty; lines that don't
+    /// parse are skipped.
+    pub fn open(data_dir: &Path) -> io::Result<Self> {
+        let mut journal = Self::default();
+        journal.sync(data_dir)?;
+        Ok(journal)
+    }
+
+    /// Entries, oldest first
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Read the entries appended since the last sync
+    pub fn sync(&mut self, data_dir: &Path) -> io::Result<()> {
+        let mut file = match fs::File::open(path(data_dir)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                *self = Self::default();
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        let metadata = file.metadata()?;
+        let file_id = Some(file_id(&metadata));
+        if file_id != self.file_id || metadata.len() < self.offset {
+            *self = Self { file_id, ..Self::default() };
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::new();
+        file.take(metadata.len() - self.offset).read_to_end(&mut bytes)?;
+        // A line still being written is left for the next sync
+        let Some(end) = bytes.iter().rposition(|b| *b == b'\n') else {
+            return Ok(());
+        };
+        for line in bytes[..end].split(|b| *b == b'\n') {
+            if let Ok(entry) = serde_json::from_slice::<JournalEntry>(line) {
+                self.latest.insert(entry.path.clone(), self.entries.len());
+                self.entries.push(entry);
+            }
+        }
+        self.offset += end as u64 + 1;
+        Ok(())
+    }
+
+    /// Append the changes in `comparison` that the journal doesn't already
+    /// hold in the same state, then drop entries older than `keep_days`
+    /// (see `compact`). Returns how many were recorded. Other writers wait,
+    /// so a compaction never drops lines appended meanwhile.
+    pub fn record(&mut self, data_dir: &Path, comparison: &Comparison, keep_days: u32) -> io::Result<usize> {
+        fs::create_dir_all(data_dir)?;
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(data_dir.join(LOCK_FILENAME))?;
+        lock.lock()?;
+        self.sync(data_dir)?;
+
+        let seen_at = chrono::Utc::now().timestamp().max(0) as u64;
+        let mut lines = String::new();
+        let mut recorded = 0;
+        for change in &comparison.changes {
+            let modified = change.new.as_ref().map(|e| e.track_mode.modified());
+            let latest = self.latest.get(&change.path).map(|&i| (self.entries[i].kind, self.entries[i].modified));
+            if latest == Some((change.kind, modified)) {
+                continue;
+            }
+            let entry = JournalEntry {
+                seen_at,
+                path: change.path.clone(),
+                kind: change.kind,
+                modified,
+                against: comparison.from.clone(),
+            };
+            lines.push_str(&serde_json::to_string(&entry)?);
+            lines.push('\n');
+            recorded += 1;
+        }
+
+        if recorded > 0 {
+            let mut file = fs::OpenOptions::new().create(true).append(true).open(path(data_dir))?;
+            file.write_all(lines.as_bytes())?;
+            self.sync(data_dir)?;
+        }
+        self.compact(data_dir, keep_days, seen_at)?;
+        Ok(recorded)
+    }
+
+    /// Rewrite the journal without the entries seen more than `keep_days`
+    /// before `now` that a newer entry for the same path has superseded.
+    /// Each path's newest entry stays, or an unchanged file would be
+    /// journaled again by the next scan. 0 keeps everything. Only called
+    /// with the writer lock held and the journal just synced.
+    fn compact(&mut self, data_dir: &Path, keep_days: u32, now: u64) -> io::Result<()> {
+        if keep_days == 0 {
+            return Ok(());
+        }
+        let cutoff = now.saturating_sub(u64::from(keep_days) * 24 * 60 * 60);
+        let keep: Vec<bool> = self.entries.iter().enumerate()
+            .map(|(i, e)| e.seen_at >= cutoff || self.latest.get(&e.path) == Some(&i))
+            .collect();
+        if keep.iter().all(|k| *k) {
+            return Ok(());
+        }
+
+        let mut content = String::new();
+        for (entry, _) in self.entries.iter().zip(&keep).filter(|(_, k)| **k) {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        write_atomic(&path(data_dir), content.as_bytes())?;
+        *self = Self::default();
+        self.sync(data_dir)
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> u64 {
+    0
+}
@@ -2,12 +2,35 @@
 // Core functionality for chamon TUI
 
 pub mod app;
+pub mod archive;
 pub mod baseline;
+pub mod browse;
+pub mod cli;
+pub mod compare;
 pub mod config;
+pub mod config_watch;
+pub mod drift;
 pub mod events;
+pub mod history;
+pub mod index;
+pub mod integrity;
+pub mod journal;
+pub mod mqtt;
+pub mod packages;
+pub mod report;
+pub mod restore;
+pub mod retention;
+pub mod search;
+pub mod security;
+pub mod state;
+pub mod summary;
+pub mod timeline;
 pub mod ui;
+pub mod util;
+pub mod validate;
 pub mod views;
 
 pub use app::{App, ViewMode};
 pub use baseline::{Baseline, create_initial_baseline};
 pub use config::Config;
+pub use index::{BaselineIndex, DeleteImpact, IndexEntry};
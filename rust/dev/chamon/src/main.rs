@@ -1,5 +1,7 @@
 // Chamon TUI main entry point
+use chamon_tui::cli::Cli;
 use chamon_tui::App;
+use clap::Parser;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
@@ -10,8 +12,22 @@ use ratatui::{
     Terminal,
 };
 use std::io;
+use std::path::Path;
+use std::process::ExitCode;
 
-fn main() -> io::Result<()> {
+fn main() -> io::Result<ExitCode> {
+    let cli = Cli::parse();
+    
+    // Subcommands run headless; no subcommand launches the TUI
+    if let Some(command) = cli.command {
+        return Ok(chamon_tui::cli::run(command, cli.config.as_deref()));
+    }
+    
+    run_tui(cli.config.as_deref())?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_tui(config_path: Option<&Path>) -> io::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -20,12 +36,20 @@ fn main() -> io::Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new();
+    let mut app = App::new(config_path);
 
     // Main event loop
     loop {
         // Process any pending progress updates from background threads
         app.process_progress_updates();
+        app.poll_live_compare();
+        app.poll_stored_compare();
+        app.poll_content_search();
+        app.poll_dashboard_refresh();
+        app.poll_config_changes();
+        app.poll_integrity_check();
+        app.poll_packages();
+        app.expire_toasts();
         
         terminal.draw(|f| {
             chamon_tui::ui::render(f, &mut app);
@@ -35,12 +59,19 @@ fn main() -> io::Result<()> {
         match crossterm::event::poll(std::time::Duration::from_millis(100)) {
             Ok(true) => {
                 match event::read()? {
-                    Event::Key(key) => {
-                        chamon_tui::events::handle_event(Event::Key(key), &mut app);
+                    event @ (Event::Key(_) | Event::Mouse(_)) => {
+                        chamon_tui::events::handle_event(event, &mut app);
                         if app.should_quit {
                             break;
                         }
                     }
+                    Event::Resize(_, _) => {
+                        // Repaint every cell at the new size on the next
+                        // draw; some terminals (phone SSH clients among
+                        // them) leave stale cells behind after reflowing
+                        terminal.autoresize()?;
+                        terminal.clear()?;
+                    }
                     _ => {}
                 }
             }
@@ -53,6 +84,14 @@ fn main() -> io::Result<()> {
         }
     }
 
+    // Stop a running scan before anything else so its thread isn't left
+    // behind; partial results are discarded
+    app.shutdown();
+    
+    // Remember the session for the next launch; failing to save shouldn't
+    // keep the terminal from being restored
+    let state_result = app.save_state();
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(
@@ -62,5 +101,9 @@ fn main() -> io::Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    if let Err(e) = state_result {
+        eprintln!("chamon: could not save session state: {}", e);
+    }
+
     Ok(())
 }
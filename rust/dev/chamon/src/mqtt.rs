@@ -0,0 +1,280 @@
+// Publishing to the local Mosquitto broker through mosquitto_pub, including
+// Home Assistant discovery for the drift sensors
+use crate::compare::Comparison;
+use crate::config::MqttConfig;
+use crate::drift::CheckReport;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Upper bound on per-change events per check, so a first run against an
+/// old baseline doesn't flood the broker
+const MAX_EVENTS: usize = 500;
+
+#[derive(Debug, Clone, Default)]
+pub struct Credentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Credentials {
+    /// `MQTT_USERNAME` and `MQTT_PASSWORD` from the secrets file, falling back
+    /// to the environment variables of the same name
+    pub fn load(secrets_file: &Path) -> Self {
+        let secrets = read_secrets(secrets_file);
+        let lookup = |key: &str| {
+            secrets.get(key).cloned()
+                .or_else(|| std::env::var(key).ok())
+                .filter(|v| !v.is_empty())
+        };
+        Self {
+            username: lookup("MQTT_USERNAME"),
+            password: lookup("MQTT_PASSWORD"),
+        }
+    }
+}
+
+/// `KEY=value` lines of a secrets file; comments and quotes are stripped.
+/// A missing file yields no secrets.
+fn read_secrets(path: &Path) -> HashMap<String, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().replace(['"', '\''], "")))
+        .collect()
+}
+
+/// Topics for this machine
+pub struct Topics {
+    /// Retained JSON summary of the last check
+    pub drift: String,
+    /// One JSON message per changed file
+    pub changes: String,
+    /// Used by `chamon mqtt test`
+    pub test: String,
+    node_id: String,
+    host: String,
+}
+
+impl Topics {
+    pub fn new(config: &MqttConfig) -> Self {
+        let host = crate::baseline::local_hostname();
+        let node_id: String = host.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        let base = config.topic.trim_end_matches('/');
+        Self {
+            drift: format!("{}/{}/drift", base, node_id),
+            changes: format!("{}/{}/changes", base, node_id),
+            test: format!("{}/{}/test", base, node_id),
+            node_id,
+            host,
+        }
+    }
+}
+
+/// Publish the result of a check: discovery configs (if enabled), the
+/// retained drift summary and, if enabled, one event per change
+pub fn publish_check(config: &MqttConfig, report: &CheckReport, comparison: &Comparison) -> io::Result<()> {
+    let credentials = Credentials::load(&config.secrets_path());
+    let topics = Topics::new(config);
+
+    if config.discovery {
+        publish_discovery(config, &credentials, &topics)?;
+    }
+    let summary = serde_json::to_string(report).map_err(io::Error::other)?;
+    publish(config, &credentials, &topics.drift, &summary, true)?;
+
+    if config.events && !comparison.changes.is_empty() {
+        let events: Vec<String> = comparison.changes.iter()
+            .take(MAX_EVENTS)
+            .map(|change| {
+                let rules: Vec<&str> = report.rules.iter()
+                    .filter(|m| m.paths.contains(&change.path))
+                    .map(|m| m.rule.as_str())
+                    .collect();
+                json!({
+                    "checked_at": report.checked_at,
+                    "against": report.against,
+                    "path": change.path,
+                    "change": change.kind,
+                    "rules": rules,
+                })
+                .to_string()
+            })
+            .collect();
+        publish_lines(config, &credentials, &topics.changes, &events)?;
+    }
+    Ok(())
+}
+
+/// Home Assistant discovery configs (retained) for a "config drift" problem
+/// binary sensor and a change-count sensor, both reading the drift topic
+pub fn publish_discovery(config: &MqttConfig, credentials: &Credentials, topics: &Topics) -> io::Result<()> {
+    let device = json!({
+        "identifiers": [format!("chamon_{}", topics.node_id)],
+        "name": format!("chamon {}", topics.host),
+        "manufacturer": "chamon",
+        "sw_version": env!("CARGO_PKG_VERSION"),
+    });
+    let prefix = config.discovery_prefix.trim_end_matches('/');
+
+    let drift = json!({
+        "name": "Config drift",
+        "unique_id": format!("chamon_{}_drift", topics.node_id),
+        "state_topic": topics.drift,
+        "value_template": "{{ 'ON' if value_json.drift else 'OFF' }}",
+        "json_attributes_topic": topics.drift,
+        "device_class": "problem",
+        "device": device,
+    });
+    publish(
+        config,
+        credentials,
+        &format!("{}/binary_sensor/chamon_{}/drift/config", prefix, topics.node_id),
+        &drift.to_string(),
+        true,
+    )?;
+
+    let changes = json!({
+        "name": "Changed files",
+        "unique_id": format!("chamon_{}_changes", topics.node_id),
+        "state_topic": topics.drift,
+        "value_template": "{{ value_json.added + value_json.modified + value_json.removed }}",
+        "unit_of_measurement": "files",
+        "state_class": "measurement",
+        "icon": "mdi:file-compare",
+        "device": device,
+    });
+    publish(
+        config,
+        credentials,
+        &format!("{}/sensor/chamon_{}/changes/config", prefix, topics.node_id),
+        &changes.to_string(),
+        true,
+    )
+}
+
+/// Publish one message to `topic` (QoS 1)
+pub fn publish(config: &MqttConfig, credentials: &Credentials, topic: &str, payload: &str, retain: bool) -> io::Result<()> {
+    let (mut command, _options) = mosquitto_pub(config, credentials, topic)?;
+    command.arg("-m").arg(payload);
+    if retain {
+        command.arg("-r");
+    }
+    let output = command.output().map_err(not_installed)?;
+    check_status(&output)
+}
+
+/// Publish each line as its own message to `topic` with a single connection
+fn publish_lines(config: &MqttConfig, credentials: &Credentials, topic: &str, lines: &[String]) -> io::Result<()> {
+    let (mut command, _options) = mosquitto_pub(config, credentials, topic)?;
+    let mut child = command
+        .arg("-l")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(not_installed)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for line in lines {
+            writeln!(stdin, "{}", line)?;
+        }
+    }
+    check_status(&child.wait_with_output()?)
+}
+
+/// The mosquitto_pub command for `topic`, plus the options file holding the
+/// credentials, which must outlive the run
+fn mosquitto_pub(config: &MqttConfig, credentials: &Credentials, topic: &str) -> io::Result<(Command, Option<OptionsFile>)> {
+    let mut command = Command::new("mosquitto_pub");
+    command
+        .arg("-h").arg(&config.host)
+        .arg("-p").arg(config.port.to_string())
+        .arg("-t").arg(topic)
+        .arg("-q").arg("1");
+    let options = OptionsFile::create(credentials)?;
+    if let Some(options) = &options {
+        command.arg("-o").arg(&options.0);
+    }
+    Ok((command, options))
+}
+
+/// Credentials for one mosquitto_pub run, passed as an options file (`-o`)
+/// readable only by us: on the command line any local user could read them
+/// from `ps` or /proc. The file is removed when this is dropped.
+struct OptionsFile(PathBuf);
+
+impl OptionsFile {
+    fn create(credentials: &Credentials) -> io::Result<Option<Self>> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        // One option per line; the value runs to the end of the line
+        let mut content = String::new();
+        for (flag, value) in [("-u", &credentials.username), ("-P", &credentials.password)] {
+            let Some(value) = value else { continue };
+            if value.contains(['\n', '\r']) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "MQTT credentials can't contain line breaks"));
+            }
+            content.push_str(&format!("{} {}\n", flag, value));
+        }
+        if content.is_empty() {
+            return Ok(None);
+        }
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let path = std::env::temp_dir().join(format!(
+            "chamon-mqtt-{}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            nanos,
+        ));
+        // create_new refuses an existing file or symlink at that name
+        let mut open = fs::OpenOptions::new();
+        open.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            open.mode(0o600);
+        }
+        let mut file = open.open(&path)?;
+        let options = Self(path);
+        file.write_all(content.as_bytes())?;
+        Ok(Some(options))
+    }
+}
+
+impl Drop for OptionsFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn not_installed(e: io::Error) -> io::Error {
+    match e.kind() {
+        io::ErrorKind::NotFound => io::Error::new(
+            io::ErrorKind::NotFound,
+            "mosquitto_pub not found (install mosquitto-clients)",
+        ),
+        _ => e,
+    }
+}
+
+fn check_status(output: &std::process::Output) -> io::Result<()> {
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(io::Error::other(format!("mosquitto_pub failed: {}", stderr.trim())))
+}
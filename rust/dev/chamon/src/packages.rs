@@ -0,0 +1,112 @@
+// Package ownership of files, read from the dpkg database
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// dpkg's per-package file lists, relative to the filesystem root
+const DPKG_INFO_DIR: &str = "var/lib/dpkg/info";
+/// dpkg's action log and its first rotation, relative to the filesystem root
+const DPKG_LOGS: &[&str] = &["var/log/dpkg.log.1", "var/log/dpkg.log"];
+
+/// Which installed package owns each file
+#[derive(Debug, Clone, Default)]
+pub struct PackageDb {
+    owners: HashMap<String, String>,
+}
+
+impl PackageDb {
+    /// Read the dpkg database of the system rooted at `root` (`/`, or the
+    /// `scan_path` of a mounted image). A missing database yields an empty map.
+    pub fn load(root: &Path) -> Self {
+        let mut owners = HashMap::new();
+        let Ok(entries) = fs::read_dir(root.join(DPKG_INFO_DIR)) else {
+            return Self { owners };
+        };
+
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str().and_then(|n| n.strip_suffix(".list")) else {
+                continue;
+            };
+            // Multi-arch packages are listed as "name:arch"
+            let package = name.split(':').next().unwrap_or(name).to_string();
+
+            let Ok(list) = fs::read_to_string(entry.path()) else { continue };
+            for path in list.lines().filter(|l| !l.is_empty() && *l != "/.") {
+                owners.insert(path.to_string(), package.clone());
+            }
+        }
+
+        Self { owners }
+    }
+
+    /// When the database under `root` last changed: dpkg replaces the file
+    /// lists on every install, upgrade and removal, which touches the
+    /// directory. None when there is no database.
+    pub fn modified(root: &Path) -> Option<std::time::SystemTime> {
+        fs::metadata(root.join(DPKG_INFO_DIR)).and_then(|m| m.modified()).ok()
+    }
+
+    /// Package owning `path` (a live-system path), if any. On merged-/usr
+    /// systems dpkg may list `/bin/x` for a file that lives at `/usr/bin/x`.
+    pub fn owner(&self, path: &str) -> Option<&str> {
+        self.owners.get(path)
+            .or_else(|| {
+                let unmerged = path.strip_prefix("/usr")?;
+                let merged_dir = ["/bin/", "/sbin/", "/lib"].iter().any(|d| unmerged.starts_with(d));
+                merged_dir.then(|| self.owners.get(unmerged)).flatten()
+            })
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+}
+
+/// An install, upgrade or removal recorded in dpkg.log
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageEvent {
+    /// Seconds since the epoch (the log is in local time)
+    pub at: u64,
+    /// "install", "upgrade" or "remove"
+    pub action: String,
+    pub package: String,
+    /// The version installed, or the one removed
+    pub version: String,
+}
+
+/// Package events from dpkg.log (and its first rotation) of the system
+/// rooted at `root`, oldest first. Missing logs yield nothing.
+pub fn read_dpkg_log(root: &Path) -> Vec<PackageEvent> {
+    let mut events = Vec::new();
+    for log in DPKG_LOGS {
+        let Ok(content) = fs::read_to_string(root.join(log)) else { continue };
+        events.extend(content.lines().filter_map(parse_dpkg_line));
+    }
+    events
+}
+
+/// Parse lines like `2024-10-17 11:30:02 upgrade openssl:arm64 3.0.11-1 3.0.13-1`
+fn parse_dpkg_line(line: &str) -> Option<PackageEvent> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [date, time, action, package, old_version, new_version, ..] = fields[..] else {
+        return None;
+    };
+    if !matches!(action, "install" | "upgrade" | "remove") {
+        return None;
+    }
+    let at = chrono::NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S")
+        .ok()?
+        .and_local_timezone(chrono::Local)
+        .earliest()?
+        .timestamp()
+        .max(0) as u64;
+    let version = if action == "remove" { old_version } else { new_version };
+    Some(PackageEvent {
+        at,
+        action: action.to_string(),
+        package: package.split(':').next().unwrap_or(package).to_string(),
+        version: version.to_string(),
+    })
+}
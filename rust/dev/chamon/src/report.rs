@@ -0,0 +1,261 @@
+// Change reports for other tools and runbooks: JSON, CSV and Markdown
+use crate::baseline::TrackMode;
+use crate::compare::{ChangeKind, Comparison, FileChange};
+use crate::packages::PackageDb;
+use crate::security::{self, Finding, Severity};
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Identifies the JSON layout; bump the number on incompatible changes
+pub const JSON_SCHEMA: &str = "chamon.changes/1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Render `comparison` in the given format. `packages` attributes files to
+/// the package that owns them (pass an empty db to skip that).
+pub fn render(comparison: &Comparison, packages: &PackageDb, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Json => to_json(comparison, packages),
+        ReportFormat::Csv => to_csv(comparison, packages),
+        ReportFormat::Markdown => to_markdown(comparison, packages),
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    schema: &'static str,
+    from: &'a str,
+    to: &'a str,
+    generated_at: String,
+    summary: JsonSummary,
+    changes: Vec<JsonChange<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSummary {
+    added: usize,
+    modified: usize,
+    removed: usize,
+    /// Changes by their highest security severity
+    security: JsonSecurity,
+}
+
+#[derive(Serialize)]
+struct JsonSecurity {
+    high: usize,
+    medium: usize,
+    low: usize,
+}
+
+#[derive(Serialize)]
+struct JsonChange<'a> {
+    path: &'a str,
+    change: &'static str,
+    package: Option<&'a str>,
+    severity: Option<Severity>,
+    findings: Vec<Finding>,
+    old: Option<JsonFile<'a>>,
+    new: Option<JsonFile<'a>>,
+}
+
+/// File metadata; content itself is left out of reports
+#[derive(Serialize)]
+struct JsonFile<'a> {
+    size: u64,
+    modified: u64,
+    mode: String,
+    owner: u32,
+    group: u32,
+    checksum: Option<&'a str>,
+}
+
+impl<'a> JsonFile<'a> {
+    fn from_track_mode(mode: &'a TrackMode) -> Self {
+        Self {
+            size: mode.size(),
+            modified: mode.modified(),
+            mode: format_mode(mode.permissions()),
+            owner: mode.owner(),
+            group: mode.group(),
+            checksum: mode.checksum(),
+        }
+    }
+}
+
+fn to_json(comparison: &Comparison, packages: &PackageDb) -> String {
+    let [low, medium, high] = security::count_by_severity(&comparison.changes);
+    let report = JsonReport {
+        schema: JSON_SCHEMA,
+        from: &comparison.from,
+        to: &comparison.to,
+        generated_at: chrono::Local::now().to_rfc3339(),
+        summary: JsonSummary {
+            added: comparison.count(ChangeKind::Added),
+            modified: comparison.count(ChangeKind::Modified),
+            removed: comparison.count(ChangeKind::Removed),
+            security: JsonSecurity { high, medium, low },
+        },
+        changes: comparison.changes.iter()
+            .map(|change| {
+                let findings = security::assess(change);
+                JsonChange {
+                    path: &change.path,
+                    change: kind_name(change.kind),
+                    package: packages.owner(&change.path),
+                    severity: security::max_severity(&findings),
+                    findings,
+                        old: change.old.as_ref().map(|e| JsonFile::from_track_mode(&e.track_mode)),
+                    new: change.new.as_ref().map(|e| JsonFile::from_track_mode(&e.track_mode)),
+                }
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+fn to_csv(comparison: &Comparison, packages: &PackageDb) -> String {
+    let mut out = String::from(
+        "change,path,package,old_size,new_size,old_mode,new_mode,old_checksum,new_checksum,severity,findings\n",
+    );
+    for change in &comparison.changes {
+        let old = change.old.as_ref().map(|e| &e.track_mode);
+        let new = change.new.as_ref().map(|e| &e.track_mode);
+        let findings = security::assess(change);
+        let fields = [
+            kind_name(change.kind).to_string(),
+            change.path.clone(),
+            packages.owner(&change.path).unwrap_or_default().to_string(),
+            old.map(|m| m.size().to_string()).unwrap_or_default(),
+            new.map(|m| m.size().to_string()).unwrap_or_default(),
+            old.map(|m| format_mode(m.permissions())).unwrap_or_default(),
+            new.map(|m| format_mode(m.permissions())).unwrap_or_default(),
+            old.and_then(TrackMode::checksum).unwrap_or_default().to_string(),
+            new.and_then(TrackMode::checksum).unwrap_or_default().to_string(),
+            security::max_severity(&findings).map(|s| s.label()).unwrap_or_default().to_string(),
+            findings.iter().map(|f| f.message).collect::<Vec<_>>().join("; "),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Changes grouped by owning package, or by directory for unpackaged files,
+/// with content diffs collapsed under `<details>`
+fn to_markdown(comparison: &Comparison, packages: &PackageDb) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Changes: {} → {}", comparison.from, comparison.to);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Added | Modified | Removed |");
+    let _ = writeln!(out, "|------:|---------:|--------:|");
+    let _ = writeln!(
+        out,
+        "| {} | {} | {} |",
+        comparison.count(ChangeKind::Added),
+        comparison.count(ChangeKind::Modified),
+        comparison.count(ChangeKind::Removed),
+    );
+    let [low, medium, high] = security::count_by_severity(&comparison.changes);
+    if low + medium + high > 0 {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "**Security:** {} high, {} medium, {} low", high, medium, low);
+    }
+
+    let mut groups: BTreeMap<String, Vec<&FileChange>> = BTreeMap::new();
+    for change in &comparison.changes {
+        let group = match packages.owner(&change.path) {
+            Some(package) => format!("Package `{}`", package),
+            None => format!("`{}`", parent_dir(&change.path)),
+        };
+        groups.entry(group).or_default().push(change);
+    }
+
+    for (group, changes) in groups {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "## {} ({})", group, changes.len());
+        let _ = writeln!(out);
+        for change in changes {
+            let _ = writeln!(out, "- **{}** `{}`", change.kind.symbol(), change.path);
+            for finding in security::assess(change) {
+                let _ = writeln!(out, "  - ⚠ {}: {}", finding.severity.label(), finding.message);
+            }
+            if let Some(diff) = content_diff(change) {
+                let _ = writeln!(out);
+                let _ = writeln!(out, "  <details><summary>diff</summary>");
+                let _ = writeln!(out);
+                let _ = writeln!(out, "  ```diff");
+                for line in diff.lines() {
+                    let _ = writeln!(out, "  {}", line);
+                }
+                let _ = writeln!(out, "  ```");
+                let _ = writeln!(out);
+                let _ = writeln!(out, "  </details>");
+                let _ = writeln!(out);
+            }
+        }
+    }
+    out
+}
+
+/// Unified diff of a modified content-tracked file, if both sides have content
+pub fn content_diff(change: &FileChange) -> Option<String> {
+    let old = change.old.as_ref()?.track_mode.content()?;
+    let new = change.new.as_ref()?.track_mode.content()?;
+    if old == new {
+        return None;
+    }
+    Some(
+        TextDiff::from_lines(old, new)
+            .unified_diff()
+            .context_radius(3)
+            .header("old", "new")
+            .to_string(),
+    )
+}
+
+fn kind_name(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Modified => "modified",
+        ChangeKind::Removed => "removed",
+    }
+}
+
+/// Permission bits in octal, e.g. "0644"
+fn format_mode(permissions: u32) -> String {
+    format!("{:04o}", permissions & 0o7777)
+}
+
+fn parent_dir(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(pos) => &path[..pos],
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,177 @@
+// Restoring files to the state recorded in a baseline
+use crate::baseline::{FileEntry, TrackMode};
+use crate::compare::materialize;
+use crate::index::BaselineIndex;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RestoreAction {
+    /// Content rewritten, with the recorded mode and ownership
+    Content,
+    /// Content matched; only mode and/or ownership were reset
+    Metadata,
+    /// Differs, but only existence was tracked, so there is nothing to restore from
+    NotTracked,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreItem {
+    pub path: String,
+    pub action: RestoreAction,
+}
+
+/// Restore the files under `paths` (files or directories, as live paths) to
+/// their state in `filename`'s baseline. Files that already match are left
+/// alone and not reported. Files added since the baseline are not deleted.
+/// With `dry_run` nothing is written and the items say what would happen.
+pub fn restore(data_dir: &Path, filename: &str, paths: &[String], dry_run: bool) -> io::Result<Vec<RestoreItem>> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let state = materialize(data_dir, &index, filename)?;
+
+    let mut entries: Vec<&FileEntry> = state.values()
+        .filter(|entry| paths.iter().any(|p| is_under(&entry.path, p)))
+        .collect();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut items = Vec::new();
+    for entry in entries {
+        let action = match plan(entry) {
+            Some(RestoreAction::Content) if !dry_run => apply(entry, true),
+            Some(RestoreAction::Metadata) if !dry_run => apply(entry, false),
+            Some(action) => action,
+            None => continue,
+        };
+        items.push(RestoreItem { path: entry.path.clone(), action });
+    }
+    Ok(items)
+}
+
+/// Whether `path` is `prefix` or inside it
+pub(crate) fn is_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    prefix.is_empty()
+        || path == prefix
+        || path.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// What restoring `entry` takes, or None when the live file already matches
+fn plan(entry: &FileEntry) -> Option<RestoreAction> {
+    #[cfg(unix)]
+    use std::os::unix::fs::MetadataExt;
+
+    let live = fs::symlink_metadata(&entry.path).ok();
+    let content_differs = match (&entry.track_mode, &live) {
+        (TrackMode::Content { checksum, .. }, Some(_)) => {
+            match fs::read(&entry.path) {
+                Ok(bytes) => format!("{:x}", Sha256::digest(&bytes)) != *checksum,
+                Err(_) => true,
+            }
+        }
+        (TrackMode::Existence { size, .. }, Some(metadata)) => metadata.len() != *size,
+        (_, None) => true,
+    };
+
+    if content_differs {
+        return Some(match entry.track_mode {
+            TrackMode::Content { .. } => RestoreAction::Content,
+            TrackMode::Existence { .. } => RestoreAction::NotTracked,
+        });
+    }
+
+    #[cfg(unix)]
+    {
+        let metadata = live?;
+        let mode = &entry.track_mode;
+        if metadata.mode() & 0o7777 != mode.permissions() & 0o7777
+            || metadata.uid() != mode.owner()
+            || metadata.gid() != mode.group()
+        {
+            return Some(RestoreAction::Metadata);
+        }
+    }
+    None
+}
+
+fn apply(entry: &FileEntry, write_content: bool) -> RestoreAction {
+    let path = Path::new(&entry.path);
+    let result = (|| -> io::Result<()> {
+        if write_content {
+            if let Some(content) = entry.track_mode.content() {
+                return replace_content(path, content.as_bytes(), &entry.track_mode);
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = &entry.track_mode;
+            // chown clears setuid/setgid, so the mode goes second
+            std::os::unix::fs::chown(path, Some(mode.owner()), Some(mode.group()))?;
+            fs::set_permissions(path, fs::Permissions::from_mode(mode.permissions() & 0o7777))?;
+        }
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) if write_content => RestoreAction::Content,
+        Ok(()) => RestoreAction::Metadata,
+        Err(e) => RestoreAction::Failed(e.to_string()),
+    }
+}
+
+/// Replace `path` with `content` owned and moded as recorded. The content is
+/// written to a new temp file beside it that only we can read, which gets
+/// its owner and mode before it is renamed into place, so a secret is never
+/// readable by others, and a failure leaves the original untouched.
+fn replace_content(path: &Path, content: &[u8], mode: &TrackMode) -> io::Result<()> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(format!(".chamon-{}-{}-{}", std::process::id(), COUNTER.fetch_add(1, Ordering::Relaxed), nanos));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    // create_new refuses an existing file or symlink at that name
+    let mut open = fs::OpenOptions::new();
+    open.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open.mode(0o600);
+    }
+    let mut file = open.open(&tmp_path)?;
+    let tmp = TempFile(tmp_path);
+
+    file.write_all(content)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // chown clears setuid/setgid, so the mode goes second
+        std::os::unix::fs::fchown(&file, Some(mode.owner()), Some(mode.group()))?;
+        file.set_permissions(fs::Permissions::from_mode(mode.permissions() & 0o7777))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    file.sync_all()?;
+    fs::rename(&tmp.0, path)?;
+    std::mem::forget(tmp);
+    Ok(())
+}
+
+/// Removes a temp file that never made it into place
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
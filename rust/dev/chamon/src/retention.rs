@@ -0,0 +1,196 @@
+// Retention policy and pruning of delta baselines
+use crate::baseline::Baseline;
+use crate::config::RetentionConfig;
+use crate::index::{BaselineIndex, IndexEntry};
+use chrono::{DateTime, Datelike, Local, TimeZone};
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+/// Result of applying a retention policy to the stored deltas
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPlan {
+    /// Deltas that survive, newest first
+    pub keep: Vec<IndexEntry>,
+    /// Deltas to squash into their successor, newest first
+    pub prune: Vec<IndexEntry>,
+}
+
+/// Decide which deltas to keep. The initial baseline is not part of the
+/// plan; pinned deltas and the newest delta (the head of the chain, which
+/// has no successor to squash into) are always kept.
+pub fn plan(index: &BaselineIndex, policy: &RetentionConfig, now: DateTime<Local>) -> RetentionPlan {
+    let mut deltas = index.deltas_oldest_first();
+    deltas.reverse(); // Newest first: the first delta seen per day/week is the newest
+    
+    let mut keep: HashSet<&str> = HashSet::new();
+    
+    if let Some(head) = deltas.first() {
+        keep.insert(&head.filename);
+    }
+    
+    for entry in deltas.iter().take(policy.keep_last) {
+        keep.insert(&entry.filename);
+    }
+    
+    for entry in deltas.iter().filter(|e| e.pinned) {
+        keep.insert(&entry.filename);
+    }
+    
+    let today = now.date_naive();
+    let mut seen_days = HashSet::new();
+    let mut seen_weeks = HashSet::new();
+    for entry in &deltas {
+        let Some(created) = Local.timestamp_opt(entry.created_at as i64, 0).single() else {
+            continue;
+        };
+        let day = created.date_naive();
+        let age_days = (today - day).num_days();
+        
+        if age_days < policy.keep_daily as i64 && seen_days.insert(day) {
+            keep.insert(&entry.filename);
+        }
+        
+        let week = day.iso_week();
+        if age_days / 7 < policy.keep_weekly as i64 && seen_weeks.insert((week.year(), week.week())) {
+            keep.insert(&entry.filename);
+        }
+    }
+    
+    let (kept, pruned): (Vec<&IndexEntry>, Vec<&IndexEntry>) = deltas
+        .into_iter()
+        .partition(|e| keep.contains(e.filename.as_str()));
+    
+    RetentionPlan {
+        keep: kept.into_iter().cloned().collect(),
+        prune: pruned.into_iter().cloned().collect(),
+    }
+}
+
+/// Squash every pruned delta into its successor, oldest first, so each one
+/// folds into a delta that is still on disk. Returns how many were pruned.
+pub fn apply(data_dir: &Path, plan: &RetentionPlan) -> io::Result<usize> {
+    let mut pruned = 0;
+    for entry in plan.prune.iter().rev() {
+        Baseline::merge_into_successor(data_dir, &entry.filename)?;
+        pruned += 1;
+    }
+    Ok(pruned)
+}
+
+/// Plan and (unless `dry_run`) apply the retention policy
+pub fn prune(data_dir: &Path, policy: &RetentionConfig, dry_run: bool) -> io::Result<RetentionPlan> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let plan = plan(&index, policy, Local::now());
+    if !dry_run {
+        apply(data_dir, &plan)?;
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// Wednesday of ISO week 25
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 6, 18, 12, 0, 0).unwrap()
+    }
+
+    fn entry(version: &str, created: DateTime<Local>, is_delta: bool) -> IndexEntry {
+        IndexEntry {
+            version: version.to_string(),
+            filename: format!("baseline-{}.json", version),
+            created_at: created.timestamp() as u64,
+            parent: None,
+            is_delta,
+            reference: false,
+            file_count: 0,
+            size_bytes: 0,
+            label: None,
+            notes: None,
+            pinned: false,
+            scan_path: "/".to_string(),
+            remap_to: "/".to_string(),
+            host: String::new(),
+        }
+    }
+
+    /// An initial baseline a year old plus a delta per `(version, age)`
+    fn index(deltas: &[(&str, Duration)]) -> BaselineIndex {
+        let mut index = BaselineIndex::default();
+        index.entries.push(entry("initial", now() - Duration::days(365), false));
+        for (version, age) in deltas {
+            index.entries.push(entry(version, now() - *age, true));
+        }
+        index
+    }
+
+    fn policy(keep_last: usize, keep_daily: u32, keep_weekly: u32) -> RetentionConfig {
+        RetentionConfig { keep_last, keep_daily, keep_weekly, keep_journal_days: 0 }
+    }
+
+    fn versions(entries: &[IndexEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.version.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_the_newest_deltas() {
+        let index = index(&[
+            ("d1", Duration::hours(5)),
+            ("d2", Duration::hours(4)),
+            ("d3", Duration::hours(3)),
+            ("d4", Duration::hours(2)),
+        ]);
+        let plan = plan(&index, &policy(2, 0, 0), now());
+        assert_eq!(versions(&plan.keep), ["d4", "d3"]);
+        assert_eq!(versions(&plan.prune), ["d2", "d1"]);
+    }
+
+    #[test]
+    fn always_keeps_the_head_and_pinned_deltas() {
+        let mut index = index(&[
+            ("d1", Duration::days(30)),
+            ("d2", Duration::days(20)),
+            ("d3", Duration::days(10)),
+        ]);
+        index.entries.iter_mut().find(|e| e.version == "d1").unwrap().pinned = true;
+        let plan = plan(&index, &policy(0, 0, 0), now());
+        assert_eq!(versions(&plan.keep), ["d3", "d1"]);
+        assert_eq!(versions(&plan.prune), ["d2"]);
+    }
+
+    #[test]
+    fn keeps_the_newest_delta_of_each_recent_day() {
+        let index = index(&[
+            ("old", Duration::days(5)),
+            ("yesterday", Duration::days(1)),
+            ("morning", Duration::hours(3)),
+            ("noon", Duration::hours(1)),
+        ]);
+        let plan = plan(&index, &policy(0, 3, 0), now());
+        assert_eq!(versions(&plan.keep), ["noon", "yesterday"]);
+        assert_eq!(versions(&plan.prune), ["morning", "old"]);
+    }
+
+    #[test]
+    fn keeps_the_newest_delta_of_each_recent_week() {
+        // Three days back is last Sunday, still in week 24 with eight days back
+        let index = index(&[
+            ("three-weeks", Duration::days(20)),
+            ("week-24-early", Duration::days(8)),
+            ("week-24-late", Duration::days(3)),
+            ("today", Duration::hours(1)),
+        ]);
+        let plan = plan(&index, &policy(0, 0, 2), now());
+        assert_eq!(versions(&plan.keep), ["today", "week-24-late"]);
+        assert_eq!(versions(&plan.prune), ["week-24-early", "three-weeks"]);
+    }
+
+    #[test]
+    fn leaves_the_initial_baseline_out() {
+        let plan = plan(&index(&[]), &policy(5, 7, 4), now());
+        assert!(plan.keep.is_empty() && plan.prune.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+// Search: fuzzy matching of tracked paths and full-text search of the
+// content stored in baselines
+use crate::baseline::{Baseline, ScanControl};
+use crate::compare::{Comparison, FileState};
+use crate::index::BaselineIndex;
+use std::io;
+use std::path::Path;
+
+/// Results kept per search; enough to scroll through, small enough to render
+const MAX_HITS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Fuzzy match on paths in the active baseline and the current changes
+    Paths,
+    /// Substring match on content stored in any baseline
+    Contents,
+}
+
+impl SearchMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchMode::Paths => "paths",
+            SearchMode::Contents => "contents",
+        }
+    }
+}
+
+/// Where a hit was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum HitSource {
+    /// A file recorded in a baseline
+    Baseline { filename: String, version: String },
+    /// A file in the comparison shown in the Changes view
+    Change,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub source: HitSource,
+    pub path: String,
+    /// Higher is better; hits are sorted by it
+    pub score: i64,
+    /// Character positions in `path` the query matched (path search)
+    pub positions: Vec<usize>,
+    /// Line number (from 1) and text of the matching line (content search)
+    pub line: Option<(usize, String)>,
+}
+
+/// A finished search and the cursor in its results
+#[derive(Debug, Clone)]
+pub struct SearchResults {
+    pub mode: SearchMode,
+    pub query: String,
+    pub hits: Vec<SearchHit>,
+    /// Index into `hits`
+    pub selected: usize,
+    /// More matches were found than kept
+    pub truncated: bool,
+}
+
+impl SearchResults {
+    fn new(mode: SearchMode, query: &str, mut hits: Vec<SearchHit>) -> Self {
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+        let truncated = hits.len() > MAX_HITS;
+        hits.truncate(MAX_HITS);
+        Self { mode, query: query.to_string(), hits, selected: 0, truncated }
+    }
+
+    pub fn selected_hit(&self) -> Option<&SearchHit> {
+        self.hits.get(self.selected)
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.hits.len() {
+            self.selected += 1;
+        }
+    }
+}
+
+/// Score `query` as a fuzzy (in-order, case-insensitive subsequence) match
+/// of `path`, with the matched character positions. Consecutive matches and
+/// matches at the start of a path component or in the file name score
+/// higher; skipped characters cost a little.
+pub fn fuzzy_match(query: &str, path: &str) -> Option<(i64, Vec<usize>)> {
+    // One char per char, so positions index into `path`'s characters
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).map(lower).collect();
+    let chars: Vec<char> = path.chars().map(lower).collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    let name_start = chars.iter().rposition(|&c| c == '/').map_or(0, |pos| pos + 1);
+    let mut best: Option<(i64, Vec<usize>)> = None;
+
+    // Greedy match from every place the first query character occurs, keeping the best
+    'starts: for start in (0..chars.len()).filter(|&i| chars[i] == query[0]) {
+        let mut positions = Vec::with_capacity(query.len());
+        let mut next = start;
+        for &wanted in &query {
+            // Later starts leave even less of the path, so none can match
+            let Some(found) = (next..chars.len()).find(|&i| chars[i] == wanted) else {
+                break 'starts;
+            };
+            positions.push(found);
+            next = found + 1;
+        }
+
+        let mut score = 0;
+        for (n, &pos) in positions.iter().enumerate() {
+            score += 16;
+            if n > 0 && positions[n - 1] + 1 == pos {
+                score += 12;
+            } else if n > 0 {
+                score -= (pos - positions[n - 1] - 1).min(12) as i64;
+            }
+            if pos == 0 || matches!(chars[pos - 1], '/' | '_' | '-' | '.') {
+                score += 8;
+            }
+            if pos >= name_start {
+                score += 4;
+            }
+        }
+        // Shorter paths win ties
+        score -= (chars.len() / 16) as i64;
+
+        if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+
+/// Fuzzy search the paths of `state` (the active baseline's files) and of
+/// `comparison`'s changes
+pub fn search_paths(
+    query: &str,
+    state: Option<(&str, &str, &FileState)>,
+    comparison: Option<&Comparison>,
+) -> SearchResults {
+    let mut hits = Vec::new();
+    if let Some(comparison) = comparison {
+        for change in &comparison.changes {
+            if let Some((score, positions)) = fuzzy_match(query, &change.path) {
+                hits.push(SearchHit {
+                    source: HitSource::Change,
+                    path: change.path.clone(),
+                    score,
+                    positions,
+                    line: None,
+                });
+            }
+        }
+    }
+    if let Some((filename, version, files)) = state {
+        for path in files.keys() {
+            if let Some((score, positions)) = fuzzy_match(query, path) {
+                hits.push(SearchHit {
+                    source: HitSource::Baseline { filename: filename.to_string(), version: version.to_string() },
+                    path: path.clone(),
+                    score,
+                    positions,
+                    line: None,
+                });
+            }
+        }
+    }
+    SearchResults::new(SearchMode::Paths, query, hits)
+}
+
+/// Case-insensitive search of the content every baseline stores, newest
+/// baseline first. Each baseline only holds the files it recorded (all of
+/// them for the initial baseline, the changed ones for a delta), so every
+/// hit is the baseline where that version of the file was captured. Hits
+/// are found in result order, so the search stops once it has more than it
+/// keeps; cancelling `control` stops it between files.
+pub fn search_contents(data_dir: &Path, query: &str, control: &ScanControl) -> io::Result<SearchResults> {
+    let index = BaselineIndex::load_or_rebuild(data_dir)?;
+    let needle = query.to_lowercase();
+    let mut entries: Vec<_> = index.entries.iter().collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+
+    let mut hits = Vec::new();
+    'baselines: for (age, entry) in entries.iter().enumerate() {
+        let baseline = Baseline::load(data_dir, &entry.filename)?;
+        let mut files: Vec<_> = baseline.files.values().collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        for file in files {
+            if control.is_cancelled() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "Search cancelled"));
+            }
+            let Some(content) = file.track_mode.content() else {
+                continue;
+            };
+            for (number, line) in content.lines().enumerate() {
+                if line.to_lowercase().contains(&needle) {
+                    hits.push(SearchHit {
+                        source: HitSource::Baseline { filename: entry.filename.clone(), version: entry.version.clone() },
+                        path: file.path.clone(),
+                        // Keeps newest baselines first, then file order
+                        score: -(age as i64),
+                        positions: Vec::new(),
+                        line: Some((number + 1, line.trim().to_string())),
+                    });
+                    if hits.len() > MAX_HITS {
+                        break 'baselines;
+                    }
+                }
+            }
+        }
+    }
+    Ok(SearchResults::new(SearchMode::Contents, query, hits))
+}
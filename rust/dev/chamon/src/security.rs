@@ -0,0 +1,125 @@
+// Security review of changes: privilege bits, permissions, ownership and
+// files that grant access (sudoers, cron, SSH keys)
+use crate::baseline::TrackMode;
+use crate::compare::{ChangeKind, FileChange};
+use crate::restore::is_under;
+use serde::Serialize;
+
+const SETUID_BIT: u32 = 0o4000;
+const SETGID_BIT: u32 = 0o2000;
+const WORLD_WRITABLE: u32 = 0o002;
+
+/// System locations where files are expected to belong to root
+const SYSTEM_PATHS: &[&str] = &["/etc", "/boot", "/usr", "/bin", "/sbin", "/lib", "/opt"];
+/// Directories whose files run as root on a schedule
+const CRON_DIRS: &[&str] = &[
+    "/etc/cron.d",
+    "/etc/cron.hourly",
+    "/etc/cron.daily",
+    "/etc/cron.weekly",
+    "/etc/cron.monthly",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+}
+
+/// Message of the finding for a newly set setuid bit; drift rules with
+/// `setuid` look for it
+pub const SETUID_SET: &str = "setuid bit set";
+
+/// A security-relevant aspect of a change
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: &'static str,
+}
+
+/// Security findings for a change, most severe first; empty for changes that
+/// don't touch anything security-relevant
+pub fn assess(change: &FileChange) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if change.kind == ChangeKind::Removed {
+        return findings;
+    }
+    let Some(new) = change.new.as_ref().map(|e| &e.track_mode) else {
+        return findings;
+    };
+    let old = change.old.as_ref().map(|e| &e.track_mode);
+    let path = change.path.as_str();
+    let system = SYSTEM_PATHS.iter().any(|p| is_under(path, p));
+    let mut add = |severity, message| findings.push(Finding { severity, message });
+
+    if gained(old, new, SETUID_BIT) {
+        add(Severity::High, SETUID_SET);
+    }
+    if gained(old, new, SETGID_BIT) {
+        add(Severity::Medium, "setgid bit set");
+    }
+    if gained(old, new, WORLD_WRITABLE) {
+        let severity = if system { Severity::High } else { Severity::Medium };
+        add(severity, "world-writable");
+    }
+
+    if system {
+        if let Some(old) = old {
+            if old.owner() == 0 && new.owner() != 0 {
+                add(Severity::High, "owner changed from root");
+            }
+            if old.group() == 0 && new.group() != 0 {
+                add(Severity::Low, "group changed from root");
+            }
+        }
+    }
+
+    if is_under(path, "/etc/sudoers.d") || path == "/etc/sudoers" {
+        let message = if change.kind == ChangeKind::Added { "new sudoers rule" } else { "sudoers rule changed" };
+        add(Severity::High, message);
+    }
+    if change.kind == ChangeKind::Added && CRON_DIRS.iter().any(|d| is_under(path, d)) {
+        add(Severity::Medium, "new cron job");
+    }
+    if path.ends_with("/.ssh/authorized_keys") || path.ends_with("/.ssh/authorized_keys2") {
+        let message = if change.kind == ChangeKind::Added { "new SSH authorized_keys" } else { "SSH authorized_keys changed" };
+        add(Severity::High, message);
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    findings
+}
+
+/// Highest severity among the findings for a change
+pub fn max_severity(findings: &[Finding]) -> Option<Severity> {
+    findings.iter().map(|f| f.severity).max()
+}
+
+/// How many changes have each severity as their highest, indexed by
+/// `Severity as usize` (low, medium, high)
+pub fn count_by_severity<'a>(changes: impl IntoIterator<Item = &'a FileChange>) -> [usize; 3] {
+    let mut counts = [0; 3];
+    for change in changes {
+        if let Some(severity) = max_severity(&assess(change)) {
+            counts[severity as usize] += 1;
+        }
+    }
+    counts
+}
+
+/// Whether `bits` are set in `new` but were not in `old` (or the file is new)
+fn gained(old: Option<&TrackMode>, new: &TrackMode, bits: u32) -> bool {
+    new.permissions() & bits != 0 && old.is_none_or(|o| o.permissions() & bits == 0)
+}
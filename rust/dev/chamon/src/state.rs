@@ -0,0 +1,44 @@
+// Persistent session state, restored on the next launch
+use crate::app::ViewMode;
+use crate::baseline::write_atomic;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// State file name, stored directly in the data dir
+pub const STATE_FILENAME: &str = "state.json";
+
+/// Session state. Baselines are referenced by filename so the state stays
+/// valid when the list order changes between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppState {
+    /// Baseline chosen as the comparison target
+    #[serde(default)]
+    pub active_baseline: Option<String>,
+    /// Baseline selected in the Baselines list
+    #[serde(default)]
+    pub selected_baseline: Option<String>,
+    #[serde(default)]
+    pub last_view: Option<ViewMode>,
+}
+
+impl AppState {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(STATE_FILENAME)
+    }
+
+    /// Read the saved state; a missing or unreadable file starts fresh
+    pub fn load(data_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(data_dir)?;
+        let json = serde_json::to_string_pretty(self)?;
+        write_atomic(&Self::path(data_dir), json.as_bytes())
+    }
+}
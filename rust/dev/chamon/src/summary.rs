@@ -0,0 +1,93 @@
+// Drift summary shown on the Dashboard: how far the live system has moved
+// from the active baseline, and where
+use crate::compare::{ChangeKind, Comparison};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// How many recently changed paths and busiest directories to keep
+const TOP_N: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct DriftSummary {
+    /// Version of the baseline the live system was compared with
+    pub against: String,
+    /// Its file name, to tell whether it is still the active baseline
+    pub baseline: String,
+    pub added: usize,
+    pub modified: usize,
+    pub removed: usize,
+    /// Added and modified files, newest modification first
+    pub recent: Vec<RecentChange>,
+    /// Directories with the most changed files directly in them
+    pub top_dirs: Vec<(String, usize)>,
+    pub scanned_at: SystemTime,
+    pub scan_duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    /// Modification time (seconds since the epoch)
+    pub modified: u64,
+}
+
+impl DriftSummary {
+    pub fn new(comparison: &Comparison, baseline: &str, scanned_at: SystemTime, scan_duration: Duration) -> Self {
+        let mut recent: Vec<RecentChange> = comparison.changes.iter()
+            .filter_map(|change| {
+                let entry = change.new.as_ref()?;
+                Some(RecentChange {
+                    path: change.path.clone(),
+                    kind: change.kind,
+                    modified: entry.track_mode.modified(),
+                })
+            })
+            .collect();
+        recent.sort_by(|a, b| b.modified.cmp(&a.modified).then_with(|| a.path.cmp(&b.path)));
+        recent.truncate(TOP_N);
+
+        let mut churn: HashMap<&str, usize> = HashMap::new();
+        for change in &comparison.changes {
+            *churn.entry(parent_dir(&change.path)).or_default() += 1;
+        }
+        let mut top_dirs: Vec<(String, usize)> = churn.into_iter()
+            .map(|(dir, count)| (dir.to_string(), count))
+            .collect();
+        top_dirs.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_dirs.truncate(TOP_N);
+
+        Self {
+            against: comparison.from.clone(),
+            baseline: baseline.to_string(),
+            added: comparison.count(ChangeKind::Added),
+            modified: comparison.count(ChangeKind::Modified),
+            removed: comparison.count(ChangeKind::Removed),
+            recent,
+            top_dirs,
+            scanned_at,
+            scan_duration,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.added + self.modified + self.removed
+    }
+}
+
+fn parent_dir(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(pos) => &path[..pos],
+    }
+}
+
+/// Rough age for display, e.g. "5m ago", "3h ago", "2d ago"
+pub fn ago(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
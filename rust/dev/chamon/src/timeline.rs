@@ -0,0 +1,116 @@
+// Timeline: baseline creation, recorded file changes and package events
+// merged into one history, newest first
+use crate::app::BaselineItem;
+use crate::compare::ChangeKind;
+use crate::journal::JournalEntry;
+use crate::packages::PackageEvent;
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    Baseline { filename: String, version: String, initial: bool, file_count: usize },
+    File { path: String, change: ChangeKind },
+    Package { action: String, package: String, version: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Seconds since the epoch
+    pub at: u64,
+    pub kind: EventKind,
+}
+
+/// All events, newest first
+pub fn build(baselines: &[BaselineItem], journal: &[JournalEntry], packages: &[PackageEvent]) -> Vec<Event> {
+    // References belong to other machines, not this one's history
+    let mut events: Vec<Event> = baselines.iter()
+        .filter(|b| !b.is_reference)
+        .map(|b| Event {
+            at: b.created_at,
+            kind: EventKind::Baseline {
+                filename: b.filename.clone(),
+                version: b.version.clone(),
+                initial: b.is_initial,
+                file_count: b.file_count,
+            },
+        })
+        .chain(journal.iter().map(|entry| Event {
+            at: entry.happened_at(),
+            kind: EventKind::File { path: entry.path.clone(), change: entry.kind },
+        }))
+        .chain(packages.iter().map(|event| Event {
+            at: event.at,
+            kind: EventKind::Package {
+                action: event.action.clone(),
+                package: event.package.clone(),
+                version: event.version.clone(),
+            },
+        }))
+        .collect();
+    events.sort_by_key(|event| std::cmp::Reverse(event.at));
+    events
+}
+
+/// One line group of the timeline
+#[derive(Debug, Clone, PartialEq)]
+pub enum Row {
+    /// Heading starting a day ("Today", "Yesterday", "Oct 15")
+    Day(String),
+    /// Index into the events
+    Event(usize),
+}
+
+/// Events under a heading per day, for events sorted newest first
+pub fn rows(events: &[Event], today: NaiveDate) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut current = None;
+    for (index, event) in events.iter().enumerate() {
+        let day = local_time(event.at).map(|t| t.date_naive());
+        if day != current {
+            current = day;
+            rows.push(Row::Day(day.map(|d| day_label(d, today)).unwrap_or_else(|| "Unknown date".to_string())));
+        }
+        rows.push(Row::Event(index));
+    }
+    rows
+}
+
+fn day_label(day: NaiveDate, today: NaiveDate) -> String {
+    match (today - day).num_days() {
+        0 => "Today".to_string(),
+        1 => "Yesterday".to_string(),
+        _ if day.year() == today.year() => day.format("%b %d").to_string(),
+        _ => day.format("%b %d, %Y").to_string(),
+    }
+}
+
+pub fn local_time(at: u64) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp(at as i64, 0).map(|t| t.with_timezone(&Local))
+}
+
+/// File change counts for the statistics line
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub today: usize,
+    pub week: usize,
+    pub month: usize,
+}
+
+pub fn stats(events: &[Event], now: DateTime<Local>) -> Stats {
+    let mut stats = Stats::default();
+    let now_secs = now.timestamp().max(0) as u64;
+    let today = now.date_naive();
+    for event in events.iter().filter(|e| matches!(e.kind, EventKind::File { .. })) {
+        if local_time(event.at).is_some_and(|t| t.date_naive() == today) {
+            stats.today += 1;
+        }
+        let age = now_secs.saturating_sub(event.at);
+        if age < 7 * 86400 {
+            stats.week += 1;
+        }
+        if age < 30 * 86400 {
+            stats.month += 1;
+        }
+    }
+    stats
+}
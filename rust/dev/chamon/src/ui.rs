@@ -1,6 +1,6 @@
 // Chamon UI rendering
-use crate::app::{App, ViewMode};
-use crate::views::{render_dashboard, render_changes, render_baselines, render_popup};
+use crate::app::{App, Hit, ViewMode};
+use crate::views::{render_dashboard, render_changes, render_baselines, render_timeline, render_search, render_history, render_popup, is_narrow};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::Style,
@@ -13,9 +13,14 @@ use tui_components::DimmingContext;
 
 pub fn render(f: &mut Frame, app: &App) {
     let area = f.area();
+    // Views record their clickable regions as they draw
+    app.hits.clear();
     
-    // Create dimming context once - this centralizes all dimming logic
-    let dimming = DimmingContext::new(app.popup.is_some());
+    // Create dimming context once - this centralizes all dimming logic.
+    // Search results and file history dim the views behind them like a
+    // popup does.
+    let overlay = app.search.is_some() || app.history.is_some();
+    let dimming = DimmingContext::new(app.popup.is_some() || overlay);
     
     // Layout: title bar (3 lines), content (with tab bar on border), status
     let chunks = Layout::default()
@@ -54,27 +59,41 @@ pub fn render(f: &mut Frame, app: &App) {
         ViewMode::Dashboard => render_dashboard(f, content_area, app, &dimming),
         ViewMode::Changes => render_changes(f, content_area, app, &dimming),
         ViewMode::Baselines => render_baselines(f, content_area, app, &dimming),
+        ViewMode::Timeline => render_timeline(f, content_area, app, &dimming),
     }
 
     // Tab bar - render on top of the content box's top border
-    let tab_items: Vec<TabBarItem> = app
-        .get_tab_items()
-        .iter()
-        .map(|(name, view)| TabBarItem {
-            name: name.clone(),
-            active: *view == app.current_view,
-        })
-        .collect();
+    let tabs = app.get_tab_items();
+    let build_tab_bar = |name_len: usize, alignment: TabBarAlignment| {
+        let tab_items: Vec<TabBarItem> = tabs
+            .iter()
+            .map(|(name, view)| TabBarItem {
+                name: name.chars().take(name_len).collect(),
+                active: *view == app.current_view,
+            })
+            .collect();
 
-    // Position tab bar on top of the content area's top border
-    let tab_bar = TabBar::new(tab_items, TabBarStyle::Tab, TabBarAlignment::Center)
-        .with_position(TabBarPosition::TopOf(content_area));
+        // Position tab bar on top of the content area's top border
+        TabBar::new(tab_items, TabBarStyle::Tab, alignment)
+            .with_position(TabBarPosition::TopOf(content_area))
+    };
+    // Narrow screens get the names cut to their first four letters, pushed
+    // right so the start of the view's own title stays readable
+    let mut tab_bar = build_tab_bar(usize::MAX, TabBarAlignment::Center);
+    if tab_bar.estimate_width() + 2 > content_area.width || is_narrow(content_area) {
+        tab_bar = build_tab_bar(4, TabBarAlignment::Right);
+    }
 
-    tab_bar.render(f);
+    let tab_rects = tab_bar.render(f);
+    for ((_, view), rect) in tabs.iter().zip(tab_rects) {
+        app.hits.push(rect, Hit::Tab(*view));
+    }
 
     // Status bar
     let status_text = if dimming.modal_visible {
         "Modal active - Use arrow keys to navigate, Enter to confirm, Esc to cancel"
+    } else if app.comparison_stale {
+        "Status: Comparison stale (config rules changed) | Press PgUp/PgDn to switch tabs | 'q' to quit"
     } else {
         "Status: Ready | Press PgUp/PgDn to switch tabs | 'q' to quit"
     };
@@ -82,8 +101,25 @@ pub fn render(f: &mut Frame, app: &App) {
         .style(Style::default().fg(dimming.text_color(false)));  // Use dimming context
     f.render_widget(status, chunks[2]); // chunks[2] is the status bar (3 constraints = 3 chunks: 0, 1, 2)
 
+    // Toasts sit on the status line, right-aligned
+    tui_components::render_toasts(f, chunks[2], &app.toasts);
+
+    // Search results and history overlay the views, and are dimmed in turn
+    // by a popup
+    let overlay_dimming = DimmingContext::new(app.popup.is_some());
+    if overlay {
+        // Only the overlay takes clicks while it's open
+        app.hits.clear();
+    }
+    render_search(f, area, app, &overlay_dimming);
+    render_history(f, area, app, &overlay_dimming);
+
     // Render popup last (overlays everything)
     if app.popup.is_some() {
-        render_popup(f, area, app);
+        let buttons = render_popup(f, area, app);
+        app.hits.clear();
+        for (index, rect) in buttons.into_iter().enumerate() {
+            app.hits.push(rect, Hit::PopupButton(index));
+        }
     }
 }
@@ -0,0 +1,440 @@
+// Configuration validation with file and line diagnostics
+use crate::config::{Config, ConfigSource, LoadedConfig};
+use crate::restore::is_under;
+use serde_yaml::Value;
+use std::fmt;
+use std::path::Path;
+
+/// Keys chamon understands, per section. Anything else is a typo or a
+/// setting from a newer version, and serde would silently ignore it.
+const TOP_LEVEL_KEYS: &[&str] = &["title", "data_dir", "baseline", "retention", "check", "mqtt", "dashboard"];
+const BASELINE_KEYS: &[&str] = &[
+    "scan_path",
+    "remap_to",
+    "exclude_directories",
+    "existence_only_directories",
+    "existence_only_extensions",
+    "content_size_limit",
+    "exclusion_log",
+];
+const RETENTION_KEYS: &[&str] = &["keep_last", "keep_daily", "keep_weekly", "keep_journal_days"];
+const CHECK_KEYS: &[&str] = &["log_file", "rules"];
+const DRIFT_RULE_KEYS: &[&str] = &["name", "paths", "changes", "setuid"];
+const DASHBOARD_KEYS: &[&str] = &["refresh_minutes"];
+const MQTT_KEYS: &[&str] = &[
+    "enabled",
+    "host",
+    "port",
+    "topic",
+    "secrets_file",
+    "discovery",
+    "discovery_prefix",
+    "events",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single validation finding. `source` is the config file (or environment
+/// variable) it came from; `"defaults"` when no file sets the value.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub source: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}: {}", self.source, line, self.severity, self.message),
+            None => write!(f, "{}: {}: {}", self.source, self.severity, self.message),
+        }
+    }
+}
+
+/// Validate a loaded configuration: load errors and warnings, unknown keys in each file,
+/// and problems with the merged settings
+pub fn validate(loaded: &LoadedConfig) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = loaded.errors.iter()
+        .map(|e| Diagnostic {
+            severity: Severity::Error,
+            source: e.source.clone(),
+            line: e.line,
+            message: e.message.clone(),
+        })
+        .collect();
+    diagnostics.extend(loaded.warnings.iter().map(|w| Diagnostic {
+        severity: Severity::Warning,
+        source: w.source.clone(),
+        line: w.line,
+        message: w.message.clone(),
+    }));
+
+    for source in &loaded.sources {
+        check_unknown_keys(source, &mut diagnostics);
+    }
+    check_settings(&loaded.config, &loaded.sources, &mut diagnostics);
+
+    diagnostics
+}
+
+/// Whether any diagnostic is an error (rather than a warning)
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+fn check_unknown_keys(source: &ConfigSource, diagnostics: &mut Vec<Diagnostic>) {
+    let Ok(Value::Mapping(root)) = serde_yaml::from_str::<Value>(&source.content) else {
+        return;
+    };
+
+    for (key, value) in &root {
+        let Some(key) = key.as_str() else { continue };
+        if !TOP_LEVEL_KEYS.contains(&key) {
+            diagnostics.push(at_key(source, &[key], Severity::Error, format!("unknown key `{}`", key)));
+            continue;
+        }
+
+        let known = match key {
+            "baseline" => BASELINE_KEYS,
+            "retention" => RETENTION_KEYS,
+            "check" => CHECK_KEYS,
+            "mqtt" => MQTT_KEYS,
+            "dashboard" => DASHBOARD_KEYS,
+            _ => continue,
+        };
+        if let Value::Mapping(section) = value {
+            for child in section.keys().filter_map(Value::as_str) {
+                if !known.contains(&child) {
+                    diagnostics.push(at_key(
+                        source,
+                        &[key, child],
+                        Severity::Error,
+                        format!("unknown key `{}.{}`", key, child),
+                    ));
+                }
+            }
+        }
+    }
+
+    // A misspelt `paths` would otherwise leave a rule matching everything
+    if let Some(Value::Sequence(rules)) = root.get("check").and_then(|check| check.get("rules")) {
+        for rule in rules.iter().filter_map(Value::as_mapping) {
+            for child in rule.keys().filter_map(Value::as_str) {
+                if !DRIFT_RULE_KEYS.contains(&child) {
+                    diagnostics.push(at_key(
+                        source,
+                        &["check", "rules", child],
+                        Severity::Error,
+                        format!("unknown key `{}` in a check rule", child),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn check_settings(config: &Config, sources: &[ConfigSource], diagnostics: &mut Vec<Diagnostic>) {
+    let baseline = &config.baseline;
+
+    // Exclusions are matched against absolute (remapped) paths, so relative
+    // entries never match anything
+    for (key, paths) in [
+        ("exclude_directories", &baseline.exclude_directories),
+        ("existence_only_directories", &baseline.existence_only_directories),
+    ] {
+        for path in paths.iter().filter(|p| !p.starts_with('/')) {
+            diagnostics.push(locate_item(
+                sources,
+                &["baseline", key],
+                path,
+                Severity::Error,
+                format!("`{}` in {} is not an absolute path", path, key),
+            ));
+        }
+    }
+
+    // Exclusion wins, so an existence-only rule inside an excluded
+    // directory never applies
+    for path in &baseline.existence_only_directories {
+        if let Some(excluded) = baseline.exclude_directories.iter().find(|e| is_under(path, e)) {
+            let message = if excluded.trim_end_matches('/') == path.trim_end_matches('/') {
+                format!("`{}` is both excluded and existence-only; it will be excluded", path)
+            } else {
+                format!("existence-only `{}` is inside excluded `{}` and has no effect", path, excluded)
+            };
+            diagnostics.push(locate_item(
+                sources,
+                &["baseline", "existence_only_directories"],
+                path,
+                Severity::Warning,
+                message,
+            ));
+        }
+    }
+
+    for extension in baseline.existence_only_extensions.iter().filter(|e| !e.starts_with('.')) {
+        diagnostics.push(locate_item(
+            sources,
+            &["baseline", "existence_only_extensions"],
+            extension,
+            Severity::Warning,
+            format!("extension `{}` does not start with `.`", extension),
+        ));
+    }
+
+    for (key, root) in [("scan_path", &baseline.scan_path), ("remap_to", &baseline.remap_to)] {
+        if !root.starts_with('/') {
+            diagnostics.push(locate_key(
+                sources,
+                &["baseline", key],
+                Severity::Error,
+                format!("{} `{}` is not an absolute path", key, root),
+            ));
+        }
+    }
+    if baseline.scan_path.starts_with('/') && !Path::new(&baseline.scan_path).is_dir() {
+        diagnostics.push(locate_key(
+            sources,
+            &["baseline", "scan_path"],
+            Severity::Error,
+            format!("scan root `{}` does not exist or is not a directory", baseline.scan_path),
+        ));
+    }
+
+    for rule in &config.check.rules {
+        if rule.paths.is_empty() && rule.changes.is_empty() && !rule.setuid {
+            diagnostics.push(locate_item(
+                sources,
+                &["check", "rules"],
+                &format!("name: {}", rule.name),
+                Severity::Error,
+                format!("rule `{}` sets none of paths, changes or setuid and would match every change", rule.name),
+            ));
+        }
+        for pattern in &rule.paths {
+            if !pattern.starts_with('/') && !pattern.starts_with('*') {
+                diagnostics.push(locate_item(
+                    sources,
+                    &["check", "rules", "paths"],
+                    pattern,
+                    Severity::Warning,
+                    format!("rule `{}`: `{}` is not an absolute path and will not match", rule.name, pattern),
+                ));
+            }
+            if let Err(e) = crate::drift::compile_pattern(pattern) {
+                diagnostics.push(locate_item(
+                    sources,
+                    &["check", "rules", "paths"],
+                    pattern,
+                    Severity::Error,
+                    format!("rule `{}`: invalid pattern `{}`: {}", rule.name, pattern, e),
+                ));
+            }
+        }
+    }
+
+    if let Some(dir) = &config.data_dir {
+        if !dir.is_absolute() && !dir.starts_with("~") {
+            diagnostics.push(locate_key(
+                sources,
+                &["data_dir"],
+                Severity::Error,
+                format!("data_dir `{}` is not an absolute path", dir.display()),
+            ));
+        }
+    }
+}
+
+fn at_key(source: &ConfigSource, path: &[&str], severity: Severity, message: String) -> Diagnostic {
+    Diagnostic {
+        severity,
+        source: source.path.display().to_string(),
+        line: find_line(&source.content, path, None),
+        message,
+    }
+}
+
+/// Attribute a setting to the last file that sets it (that's the one in
+/// effect after merging)
+fn locate_key(sources: &[ConfigSource], path: &[&str], severity: Severity, message: String) -> Diagnostic {
+    locate(sources, path, None, severity, message)
+}
+
+/// Attribute a list item to the last file that contains it
+fn locate_item(sources: &[ConfigSource], path: &[&str], item: &str, severity: Severity, message: String) -> Diagnostic {
+    locate(sources, path, Some(item), severity, message)
+}
+
+fn locate(sources: &[ConfigSource], path: &[&str], item: Option<&str>, severity: Severity, message: String) -> Diagnostic {
+    for source in sources.iter().rev() {
+        if let Some(line) = find_line(&source.content, path, item) {
+            return Diagnostic {
+                severity,
+                source: source.path.display().to_string(),
+                line: Some(line),
+                message,
+            };
+        }
+    }
+    Diagnostic { severity, source: "defaults".to_string(), line: None, message }
+}
+
+/// Find the 1-based line of a key (or of a list item under it) in YAML text.
+/// serde_yaml doesn't keep positions for values, so this follows block
+/// indentation; it handles the plain block style chamon's config uses.
+fn find_line(content: &str, path: &[&str], item: Option<&str>) -> Option<usize> {
+    // (indent, key) of the enclosing mappings for the current line
+    let mut stack: Vec<(usize, String)> = Vec::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        let trimmed = raw.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = raw.len() - trimmed.len();
+
+        if let Some(value) = trimmed.strip_prefix("- ") {
+            // List items belong to the innermost key at or left of their
+            // indent ("key:\n- item" is valid YAML)
+            while stack.last().is_some_and(|(i, _)| *i > indent) {
+                stack.pop();
+            }
+            if let Some(item) = item {
+                if keys_match(&stack, path) && (unquote(value) == item || unquote_pair(value) == item) {
+                    return Some(idx + 1);
+                }
+            } else if let Some((key, _)) = value.split_once(':') {
+                // The first key of a mapping in a list ("- name: x")
+                let (last, parents) = path.split_last()?;
+                if keys_match(&stack, parents) && unquote(key) == *last {
+                    return Some(idx + 1);
+                }
+            }
+            continue;
+        }
+
+        let Some((key, _)) = trimmed.split_once(':') else { continue };
+        while stack.last().is_some_and(|(i, _)| *i >= indent) {
+            stack.pop();
+        }
+        stack.push((indent, unquote(key).to_string()));
+
+        if item.is_none() && keys_match(&stack, path) {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+fn keys_match(stack: &[(usize, String)], path: &[&str]) -> bool {
+    stack.len() == path.len() && stack.iter().zip(path).all(|((_, k), p)| k == p)
+}
+
+/// A `key: value` list item with both sides unquoted, as in `name: ssh`
+fn unquote_pair(value: &str) -> String {
+    match value.split_once(':') {
+        Some((key, value)) => format!("{}: {}", unquote(key), unquote(value)),
+        None => unquote(value).to_string(),
+    }
+}
+
+/// Strip a trailing comment and surrounding quotes from a scalar
+fn unquote(value: &str) -> &str {
+    let value = match value.find(" #") {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+    value.trim().trim_matches('"').trim_matches('\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(content: &str) -> ConfigSource {
+        ConfigSource { path: "config.yaml".into(), content: content.to_string() }
+    }
+
+    /// Diagnostics for a single config file, as `(line, message)`
+    fn diagnose(content: &str) -> Vec<(Option<usize>, String)> {
+        let source = source(content);
+        let config: Config = serde_yaml::from_str(content).unwrap();
+        let mut diagnostics = Vec::new();
+        check_unknown_keys(&source, &mut diagnostics);
+        check_settings(&config, &[source], &mut diagnostics);
+        diagnostics.into_iter().map(|d| (d.line, d.message)).collect()
+    }
+
+    const CONFIG: &str = "\
+# chamon
+title: test
+baseline:
+  scan_path: /  # the root
+  exclude_directories:
+    - /proc
+    - \"/sys\"
+  existence_only_extensions:
+  - .log
+check:
+  rules:
+    - name: ssh
+      paths: [/etc/ssh]
+";
+
+    #[test]
+    fn finds_keys_by_path() {
+        assert_eq!(find_line(CONFIG, &["title"], None), Some(2));
+        assert_eq!(find_line(CONFIG, &["baseline", "scan_path"], None), Some(4));
+        assert_eq!(find_line(CONFIG, &["check", "rules", "paths"], None), Some(13));
+        assert_eq!(find_line(CONFIG, &["scan_path"], None), None);
+        assert_eq!(find_line(CONFIG, &["baseline", "remap_to"], None), None);
+    }
+
+    #[test]
+    fn finds_list_items() {
+        assert_eq!(find_line(CONFIG, &["baseline", "exclude_directories"], Some("/proc")), Some(6));
+        assert_eq!(find_line(CONFIG, &["baseline", "exclude_directories"], Some("/sys")), Some(7));
+        // Items level with their key
+        assert_eq!(find_line(CONFIG, &["baseline", "existence_only_extensions"], Some(".log")), Some(9));
+        assert_eq!(find_line(CONFIG, &["baseline", "existence_only_directories"], Some("/proc")), None);
+    }
+
+    #[test]
+    fn finds_mappings_in_lists() {
+        assert_eq!(find_line(CONFIG, &["check", "rules"], Some("name: ssh")), Some(12));
+        assert_eq!(find_line(CONFIG, &["check", "rules", "name"], None), Some(12));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_in_check_rules() {
+        let diagnostics = diagnose("check:\n  rules:\n    - name: ssh\n      path: [/etc/ssh]\n");
+        assert!(diagnostics.contains(&(Some(4), "unknown key `path` in a check rule".to_string())));
+    }
+
+    #[test]
+    fn rejects_rules_that_match_everything() {
+        let diagnostics = diagnose("check:\n  rules:\n    - name: \"ssh\"\n");
+        assert!(diagnostics.contains(&(
+            Some(3),
+            "rule `ssh` sets none of paths, changes or setuid and would match every change".to_string(),
+        )));
+
+        let diagnostics = diagnose("check:\n  rules:\n    - name: suid\n      setuid: true\n");
+        assert!(diagnostics.iter().all(|(_, message)| !message.contains("would match every change")));
+    }
+}
@@ -1,6 +1,10 @@
 // Baselines view
-use crate::app::App;
-use tui_components::DimmingContext;
+use crate::app::{App, Hit, Pane};
+use crate::baseline::TrackMode;
+use crate::browse::{self, Row};
+use crate::compare::ChangeKind;
+use crate::util::format_size;
+use tui_components::{ellipsize_middle, DimmingContext};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -27,22 +31,34 @@ pub fn render_baselines(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
     };
     
     // Layout: main content area and bindings box at bottom
+    let (bindings, bindings_height) = super::bindings_box(binding_lines(app, dimming), inner_area.width, dimming);
     let main_chunks = Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
         .constraints([
-            Constraint::Min(0),        // Main content (two columns)
-            Constraint::Length(3),     // Bindings box
+            Constraint::Min(0),                  // Main content (two columns)
+            Constraint::Length(bindings_height), // Bindings box
         ])
         .split(inner_area);
 
-    // Two-column layout: Entries (left) and Display (right)
-    let columns = Layout::default()
-        .direction(ratatui::layout::Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Entries panel
-            Constraint::Percentage(50), // Display panel
-        ])
-        .split(main_chunks[0]);
+    // Two-column layout: Entries (left) and Display (right), or Entries
+    // above Display on narrow screens
+    let columns = if super::is_narrow(area) {
+        Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(35), // Entries panel
+                Constraint::Percentage(65), // Display panel
+            ])
+            .split(main_chunks[0])
+    } else {
+        Layout::default()
+            .direction(ratatui::layout::Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(50), // Entries panel
+                Constraint::Percentage(50), // Display panel
+            ])
+            .split(main_chunks[0])
+    };
 
     // Left column: Entries (baseline list)
     render_baseline_list(f, columns[0], app, dimming);
@@ -51,7 +67,7 @@ pub fn render_baselines(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
     render_display_panel(f, columns[1], app, dimming);
     
     // Bindings box at bottom
-    render_bindings_box(f, main_chunks[1], dimming);
+    f.render_widget(bindings, main_chunks[1]);
 }
 
 fn render_baseline_list(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
@@ -63,6 +79,15 @@ fn render_baseline_list(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
         .title_alignment(ratatui::layout::Alignment::Left)
         .border_style(Style::default().fg(dimming.border_color(true)));
 
+    // Marked baselines show which side of the split comparison they take
+    let marked_pair = app.marked_pair().map(|(a, b)| (a.filename.clone(), b.filename.clone()));
+    let mark_of = |filename: &String| match &marked_pair {
+        Some((a, _)) if a == filename => Some("[A] "),
+        Some((_, b)) if b == filename => Some("[B] "),
+        _ if app.marked_baselines.contains(filename) => Some("[A] "),
+        _ => None,
+    };
+    
     let items: Vec<ListItem> = app
         .baselines
         .iter()
@@ -80,17 +105,42 @@ fn render_baseline_list(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
                 spans.push(Span::raw("   "));
             }
             
+            if let Some(mark) = mark_of(&baseline.filename) {
+                spans.push(Span::styled(
+                    mark,
+                    Style::default().fg(dimming.dim_color(Color::Magenta)).add_modifier(Modifier::BOLD),
+                ));
+            }
+            
             // Version - use dimming context
             spans.push(Span::styled(
                 baseline.version.clone(),
                 Style::default().fg(dimming.text_color(true)),
             ));
             
-            // File count with proper spacing
-            let count_text = if baseline.is_initial {
-                format!("   ({} files)", baseline.file_count)
+            // Pinned (protected) marker
+            if baseline.pinned {
+                spans.push(Span::styled(
+                    " [pinned]",
+                    Style::default().fg(dimming.dim_color(Color::Yellow)),
+                ));
+            }
+            
+            // Label (if any) after the version
+            if let Some(label) = &baseline.label {
+                spans.push(Span::styled(
+                    format!("  \"{}\"", label),
+                    Style::default().fg(dimming.dim_color(Color::Cyan)),
+                ));
+            }
+            
+            // File count and size on disk with proper spacing
+            let count_text = if baseline.is_reference {
+                format!("   (reference from {}, {} files, {})", baseline.host, baseline.file_count, format_size(baseline.size_bytes))
+            } else if baseline.is_initial {
+                format!("   ({} files, {})", baseline.file_count, format_size(baseline.size_bytes))
             } else {
-                format!("   ({} changes)", baseline.file_count)
+                format!("   ({} changes, {})", baseline.file_count, format_size(baseline.size_bytes))
             };
             
             spans.push(Span::styled(
@@ -103,9 +153,11 @@ fn render_baseline_list(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
         })
         .collect();
 
-    // Use dimming context for selection style
-    let highlight_style = dimming.selection_style(true);
+    // Use dimming context for selection style; the file browser has the
+    // focus while it is open
+    let highlight_style = dimming.selection_style(app.baseline_browser.is_none());
 
+    let rows_area = block.inner(area);
     let list = List::new(items)
         .block(block)
         .highlight_style(highlight_style)
@@ -117,6 +169,8 @@ fn render_baseline_list(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
     state.select(Some(app.selected_baseline));
     
     f.render_stateful_widget(list, area, &mut state);
+    app.hits.push(area, Hit::Pane(Pane::Baselines));
+    app.hits.push_list(rows_area, state.offset(), app.baselines.len(), |i| Some(Hit::Row(Pane::Baselines, i)));
 }
 
 fn render_display_panel(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
@@ -124,46 +178,194 @@ fn render_display_panel(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
     if app.creating_baseline {
         render_progress_view(f, area, app, dimming);
     } else {
-        // Default placeholder content
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(dimming.border_color(true)));
+        let details = baseline_detail_lines(app, dimming);
+        // Details take what they need, up to half the panel; files get the rest
+        let height = (details.len() as u16 + 2).min(area.height / 2);
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([Constraint::Length(height), Constraint::Min(0)])
+            .split(area);
+        render_baseline_details(f, chunks[0], details, dimming);
+        render_baseline_files(f, chunks[1], app, dimming);
+    }
+}
 
-        let content_lines = vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::styled(
-                    "     Display of:",
-                    Style::default().fg(dimming.text_color(false)),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "     Baseline creation Progress",
-                    Style::default().fg(dimming.text_color(false)),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "     initial creation progress",
-                    Style::default().fg(dimming.text_color(false)),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled(
-                    "     highlevel view of contents",
-                    Style::default().fg(dimming.text_color(false)),
-                ),
-            ]),
-            Line::from(""),
-        ];
+/// Selected baseline's label, notes and protection state
+fn render_baseline_details(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>, dimming: &DimmingContext) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("─ Details ─")
+        .title_alignment(ratatui::layout::Alignment::Left)
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .alignment(ratatui::layout::Alignment::Left);
+    
+    f.render_widget(paragraph, area);
+}
 
-        let paragraph = Paragraph::new(content_lines)
-            .block(block)
-            .alignment(ratatui::layout::Alignment::Left);
+fn baseline_detail_lines(app: &App, dimming: &DimmingContext) -> Vec<Line<'static>> {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+    
+    match app.baselines.get(app.selected_baseline) {
+        Some(baseline) => {
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled("Version: ", label_style),
+                    Span::styled(baseline.version.clone(), value_style),
+                ]),
+                Line::from(vec![
+                    Span::styled("Label:   ", label_style),
+                    Span::styled(baseline.label.clone().unwrap_or_else(|| "-".to_string()), value_style),
+                ]),
+                Line::from(vec![
+                    Span::styled("Pinned:  ", label_style),
+                    Span::styled(if baseline.pinned { "yes (protected)" } else { "no" }, value_style),
+                ]),
+                Line::from(vec![
+                    Span::styled("Created: ", label_style),
+                    Span::styled(format!("{} on {}", format_timestamp(baseline.created_at), baseline.host), value_style),
+                ]),
+                Line::from(vec![
+                    Span::styled("Stored:  ", label_style),
+                    Span::styled(stored_summary(app, baseline), value_style),
+                ]),
+            ];
+            if let Some((a, b)) = app.marked_pair() {
+                lines.push(Line::from(vec![
+                    Span::styled("Marked:  ", label_style),
+                    Span::styled(format!("A {} | B {} - [s] to compare", a.version, b.version), value_style),
+                ]));
+            }
+            if let Some(comparison) = &app.comparison {
+                let stale = if app.comparison_stale { " (stale - rules changed)" } else { "" };
+                lines.push(Line::from(vec![
+                    Span::styled("Compare: ", label_style),
+                    Span::styled(
+                        format!(
+                            "{} -> {}: {} added, {} modified, {} removed{}",
+                            comparison.from,
+                            comparison.to,
+                            comparison.count(ChangeKind::Added),
+                            comparison.count(ChangeKind::Modified),
+                            comparison.count(ChangeKind::Removed),
+                            stale,
+                        ),
+                        value_style,
+                    ),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("Notes:", label_style)));
+            match &baseline.notes {
+                Some(notes) => lines.extend(notes.lines().map(|l| Line::from(Span::styled(l.to_string(), value_style)))),
+                None => lines.push(Line::from(Span::styled("-", value_style))),
+            }
+            lines
+        }
+        None => vec![Line::from(Span::styled(
+            "No baselines yet - press [i] to create the initial baseline",
+            label_style,
+        ))],
+    }
+}
 
+/// What the baseline file holds: every tracked file for the initial
+/// baseline, the changes since its parent for a delta
+fn stored_summary(app: &App, baseline: &crate::app::BaselineItem) -> String {
+    let size = format_size(baseline.size_bytes);
+    if baseline.is_reference {
+        return format!("reference from {}, {} files, {}", baseline.host, baseline.file_count, size);
+    }
+    if baseline.is_initial {
+        return format!("{} files, {}", baseline.file_count, size);
+    }
+    let parent = baseline.parent.as_ref().map(|parent| {
+        app.baselines.iter()
+            .find(|b| &b.filename == parent)
+            .map(|b| b.version.clone())
+            .unwrap_or_else(|| parent.clone())
+    });
+    match parent {
+        Some(parent) => format!("{} changes since {}, {}", baseline.file_count, parent, size),
+        None => format!("{} changes, {}", baseline.file_count, size),
+    }
+}
+
+/// The selected baseline's files as a tree, or the stored content of one
+/// of them
+fn render_baseline_files(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+    let block = |title: String| {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_alignment(ratatui::layout::Alignment::Left)
+            .border_style(Style::default().fg(dimming.border_color(true)))
+    };
+
+    let Some(browser) = &app.baseline_browser else {
+        let message = if app.baselines.is_empty() { "" } else { "[Enter] to browse the files in this baseline" };
+        f.render_widget(Paragraph::new(Span::styled(message, label_style)).block(block("─ Files ─".to_string())), area);
+        return;
+    };
+
+    if let Some(entry) = browser.viewing.and_then(|index| browser.files.get(index)) {
+        let content = entry.track_mode.content().unwrap_or_default();
+        let lines: Vec<Line> = content.lines()
+            .enumerate()
+            .map(|(number, text)| Line::from(vec![
+                Span::styled(format!("{:>4} ", number + 1), label_style),
+                Span::styled(text.to_string(), value_style),
+            ]))
+            .collect();
+        let paragraph = Paragraph::new(lines)
+            // Borders and the rest of the title take 18 columns
+            .block(block(format!("─ {} (read-only) ─", ellipsize_middle(&entry.path, (area.width as usize).saturating_sub(18)))))
+            .scroll((browser.scroll, 0));
         f.render_widget(paragraph, area);
+        app.hits.push(area, Hit::Pane(Pane::BaselineViewer));
+        return;
     }
+
+    let items: Vec<ListItem> = browser.rows.iter()
+        .map(|row| match row {
+            Row::Dir { path, depth, count, collapsed } => ListItem::new(Line::from(vec![
+                Span::raw("  ".repeat(*depth)),
+                Span::styled(if *collapsed { "▸ " } else { "▾ " }, label_style),
+                Span::styled(format!("{}/", browse::file_name(path)), value_style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" ({} file{})", count, if *count == 1 { "" } else { "s" }), label_style),
+            ])),
+            Row::File { index, depth } => {
+                let entry = &browser.files[*index];
+                let (mode, color) = match entry.track_mode {
+                    TrackMode::Content { .. } => ("content  ", Color::Cyan),
+                    TrackMode::Existence { .. } => ("exists   ", Color::DarkGray),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(*depth)),
+                    Span::styled(mode, Style::default().fg(dimming.dim_color(color))),
+                    Span::styled(browse::file_name(&entry.path).to_string(), value_style),
+                    Span::styled(format!("  {}", format_size(entry.track_mode.size())), label_style),
+                ]))
+            }
+        })
+        .collect();
+
+    let files_block = block(format!("─ Files ({}) ─", browser.files.len()));
+    let rows_area = files_block.inner(area);
+    let list = List::new(items)
+        .block(files_block)
+        .highlight_style(dimming.selection_style(true));
+    let mut state = ratatui::widgets::ListState::default();
+    state.select(Some(browser.selected));
+    f.render_stateful_widget(list, area, &mut state);
+    app.hits.push(area, Hit::Pane(Pane::BaselineFiles));
+    app.hits.push_list(rows_area, state.offset(), browser.rows.len(), |i| Some(Hit::Row(Pane::BaselineFiles, i)));
 }
 
 fn render_progress_view(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
@@ -194,9 +396,10 @@ fn render_progress_view(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingC
 }
 
 fn render_progress_overview(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let title = if app.scan_paused() { " Progress Overview (Paused) " } else { " Progress Overview " };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" Progress Overview ")
+        .title(title)
         .border_style(Style::default().fg(dimming.border_color(true)));
     
     let mut lines = Vec::new();
@@ -234,8 +437,8 @@ fn render_progress_overview(f: &mut Frame, area: Rect, app: &App, dimming: &Dimm
     ]));
     
     // Elapsed and estimated remaining time
-    if let Some(start_time) = app.baseline_start_time {
-        let elapsed = start_time.elapsed().unwrap_or_default();
+    if app.baseline_start_time.is_some() {
+        let elapsed = app.baseline_elapsed();
         let elapsed_secs = elapsed.as_secs();
         let elapsed_min = elapsed_secs / 60;
         let elapsed_sec = elapsed_secs % 60;
@@ -268,6 +471,16 @@ fn render_progress_overview(f: &mut Frame, area: Rect, app: &App, dimming: &Dimm
         Span::styled(workers_text, Style::default().fg(dimming.text_color(false))),
     ]));
     
+    // Scan controls
+    let pause_label = if app.scan_paused() { "Resume" } else { "Pause" };
+    lines.push(Line::from(vec![
+        Span::styled("[Esc] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+        Span::styled("Cancel", Style::default().fg(dimming.text_color(false))),
+        Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
+        Span::styled("[p] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+        Span::styled(pause_label, Style::default().fg(dimming.text_color(false))),
+    ]));
+    
     let paragraph = Paragraph::new(lines)
         .block(block)
         .alignment(ratatui::layout::Alignment::Left);
@@ -338,15 +551,12 @@ fn render_active_workers(f: &mut Frame, area: Rect, app: &App, dimming: &Dimming
             .iter()
             .enumerate()
             .map(|(idx, (worker_name, file_count, current_path))| {
-                let spinner = spinners[idx % spinners.len()];
+                let spinner = if app.scan_paused() { "⏸" } else { spinners[idx % spinners.len()] };
                 
-                // Truncate path if too long
-                let max_path_len = 30;
-                let display_path = if current_path.len() > max_path_len {
-                    format!("...{}", &current_path[current_path.len().saturating_sub(max_path_len - 3)..])
-                } else {
-                    current_path.clone()
-                };
+                // Shorten the path from the middle to what's left of the row
+                // after the borders, spinner, worker name and file count
+                let max_path_len = (area.width as usize).saturating_sub(worker_name.chars().count() + 32);
+                let display_path = ellipsize_middle(current_path, max_path_len);
                 
                 ListItem::new(Line::from(vec![
                     Span::styled(spinner, Style::default().fg(Color::Yellow)),
@@ -388,37 +598,48 @@ fn render_completed_directories(f: &mut Frame, area: Rect, app: &App, dimming: &
     f.render_widget(list, area);
 }
 
-fn render_bindings_box(f: &mut Frame, area: Rect, dimming: &DimmingContext) {
-    // Bindings box showing keyboard shortcuts
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title("─ Bindings ─")
-        .title_alignment(ratatui::layout::Alignment::Left)
-        .border_style(Style::default().fg(dimming.border_color(true)));
+/// Keyboard shortcuts for the bindings box, for the file browser while it
+/// is open
+fn binding_lines(app: &App, dimming: &DimmingContext) -> Vec<Line<'static>> {
+    let key = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD));
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(false)));
 
-    // Bindings text: [n] New Baseline | [i] Create Initial Baseline | [c] Compare to active | [del] Delete selected
-    let bindings_text = vec![
+    // While browsing files: [↑↓] Select | [Enter] Fold dir / View file | [Esc] Back
+    // [n] New Baseline | [i] Create Initial Baseline | [c] Compare to active | [m] Mark | [s] Compare marked side by side
+    // [a] Set active | [l] Label | [e] Notes | [p] Pin | [r] Prune | [del] Delete selected | [Enter] Browse files
+    if let Some(browser) = &app.baseline_browser {
+        let (arrows, esc) = if browser.viewing.is_some() { ("Scroll", "Close viewer") } else { ("Select", "Back to baselines") };
+        vec![Line::from(vec![
+            key("[↑↓] "), label(arrows), label(" | "),
+            key("[Enter] "), label("Fold dir / View file"), label(" | "),
+            key("[Esc] "), label(esc),
+        ])]
+    } else { vec![
         Line::from(vec![
-            Span::styled("[n] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
-            Span::styled("New Baseline", Style::default().fg(dimming.text_color(false))),
-            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
-            Span::styled("[i] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
-            Span::styled("Create Initial Baseline", Style::default().fg(dimming.text_color(false))),
-            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
-            Span::styled("[c] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
-            Span::styled("Compare to active", Style::default().fg(dimming.text_color(false))),
-            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
-            Span::styled("[del] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
-            Span::styled("Delete selected", Style::default().fg(dimming.text_color(false))),
+            key("[n] "), label("New Baseline"), label(" | "),
+            key("[i] "), label("Create Initial Baseline"), label(" | "),
+            key("[c] "), label("Compare to active"), label(" | "),
+            key("[m] "), label("Mark"), label(" | "),
+            key("[s] "), label("Compare marked side by side"),
         ]),
-    ];
-
-    let paragraph = Paragraph::new(bindings_text)
-        .block(block)
-        .alignment(ratatui::layout::Alignment::Left);
-
-    f.render_widget(paragraph, area);
+        Line::from(vec![
+            key("[a] "), label("Set active"), label(" | "),
+            key("[l] "), label("Label"), label(" | "),
+            key("[e] "), label("Notes"), label(" | "),
+            key("[p] "), label("Pin"), label(" | "),
+            key("[r] "), label("Prune"), label(" | "),
+            key("[del] "), label("Delete selected"), label(" | "),
+            key("[Enter] "), label("Browse files"),
+        ]),
+    ] }
 }
 
 // Action buttons and comparison results removed from this view
 // They may be moved to a different location or shown in the display panel later
+
+/// Local date and time of a Unix timestamp
+fn format_timestamp(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -1,22 +1,401 @@
 // Changes view
-use crate::app::App;
-use tui_components::DimmingContext;
+use crate::app::{App, Hit, Pane};
+use crate::baseline::TrackMode;
+use crate::browse::{self, Row};
+use crate::compare::{ChangeKind, FileChange};
+use crate::security::{self, Severity};
+use tui_components::{ellipsize_middle, DimmingContext};
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-pub fn render_changes(f: &mut Frame, area: Rect, _app: &App, _dimming: &DimmingContext) {
+pub fn render_changes(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let (bindings, bindings_height) = super::bindings_box(binding_lines(dimming), area.width, dimming);
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                  // Change list and details
+            Constraint::Length(bindings_height), // Bindings box
+        ])
+        .split(area);
+    f.render_widget(bindings, chunks[1]);
+
+    let Some(comparison) = &app.comparison else {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" File Changes ")
+            .border_style(Style::default().fg(dimming.border_color(true)));
+        let message = if app.live_compare.is_some() {
+            "Scanning the live system..."
+        } else {
+            "No comparison yet - press [l] to compare the live system, or select a baseline and press [c] in the Baselines tab"
+        };
+        let content = Paragraph::new(message)
+            .style(Style::default().fg(dimming.text_color(false)))
+            .wrap(Wrap { trim: false })
+            .block(block);
+        f.render_widget(content, chunks[0]);
+        return;
+    };
+
+    // Side by side puts the list on top so both panes get half the width;
+    // narrow screens stack the list and details too
+    let panes = if app.split_compare || super::is_narrow(area) {
+        Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(40), // Change list
+                Constraint::Percentage(60), // Old and new side
+            ])
+            .split(chunks[0])
+    } else {
+        Layout::default()
+            .direction(ratatui::layout::Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(55), // Change list
+                Constraint::Percentage(45), // Details
+            ])
+            .split(chunks[0])
+    };
+
+    let mut title = format!(
+        " File Changes: {} -> {} ({} added, {} modified, {} removed)",
+        comparison.from,
+        comparison.to,
+        comparison.count(ChangeKind::Added),
+        comparison.count(ChangeKind::Modified),
+        comparison.count(ChangeKind::Removed),
+    );
+    if app.change_filter.is_active() {
+        let shown = app.change_rows.iter().filter(|r| matches!(r, Row::File { .. })).count();
+        title.push_str(&format!(" [{} shown: {}]", shown, app.change_filter.describe()));
+    }
+    if app.comparison_stale {
+        title.push_str(" - stale");
+    }
+    if app.live_compare.is_some() {
+        title.push_str(" - scanning live...");
+    }
+    title.push(' ');
+
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" File Changes ")
-        .border_style(Style::default().fg(Color::Yellow));
+        .title(title)
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let rows_area = block.inner(panes[0]);
+
+    let items: Vec<ListItem> = if app.change_rows.is_empty() {
+        let message = if comparison.changes.is_empty() { "No changes" } else { "No changes match the filters" };
+        vec![ListItem::new(Span::styled(message, Style::default().fg(dimming.text_color(false))))]
+    } else {
+        app.change_rows.iter()
+            .map(|row| match row {
+                Row::Dir { path, depth, count, collapsed } => ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(*depth)),
+                    Span::styled(
+                        if *collapsed { "▸ " } else { "▾ " },
+                        Style::default().fg(dimming.text_color(false)),
+                    ),
+                    Span::styled(
+                        format!("{}/", browse::file_name(path)),
+                        Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!(" ({})", count), Style::default().fg(dimming.text_color(false))),
+                ])),
+                Row::File { index, depth } => {
+                    let change = &comparison.changes[*index];
+                    let name = if app.change_tree { browse::file_name(&change.path) } else { &change.path };
+                    // Indent, kind and severity take depth * 2 + 8 columns
+                    let room = (rows_area.width as usize).saturating_sub(depth * 2 + 8);
+                    ListItem::new(Line::from(vec![
+                        Span::raw("  ".repeat(*depth)),
+                        Span::styled(
+                            format!(" {} ", change.kind.symbol()),
+                            Style::default().fg(dimming.dim_color(kind_color(change.kind))).add_modifier(Modifier::BOLD),
+                        ),
+                        severity_span(security::max_severity(&security::assess(change)), dimming),
+                        Span::styled(ellipsize_middle(name, room), Style::default().fg(dimming.text_color(true))),
+                    ]))
+                }
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    if !app.change_rows.is_empty() {
+        state.select(Some(app.selected_change.min(app.change_rows.len() - 1)));
+    }
+    f.render_stateful_widget(list, panes[0], &mut state);
+    app.hits.push(panes[0], Hit::Pane(Pane::Changes));
+    app.hits.push_list(rows_area, state.offset(), app.change_rows.len(), |i| Some(Hit::Row(Pane::Changes, i)));
+    app.hits.push(panes[1], Hit::Pane(Pane::ChangeDetails));
+
+    match app.selected_file_change() {
+        Some(change) if app.split_compare => {
+            render_split_details(f, panes[1], change, (&comparison.from, &comparison.to), app, dimming)
+        }
+        _ => render_change_details(f, panes[1], app, dimming),
+    }
+}
 
-    let content = Paragraph::new("Changes view - TODO: Implement file changes list")
-        .block(block);
+/// The old and new side of a change next to each other. Both panes have
+/// the same rows and share the scroll offset, so lines stay aligned.
+fn render_split_details(
+    f: &mut Frame,
+    area: Rect,
+    change: &FileChange,
+    (from, to): (&str, &str),
+    app: &App,
+    dimming: &DimmingContext,
+) {
+    // Narrow screens put A above B; the rows still line up through the
+    // shared scroll offset
+    let direction = if super::is_narrow(area) {
+        ratatui::layout::Direction::Vertical
+    } else {
+        ratatui::layout::Direction::Horizontal
+    };
+    let sides = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    // Inside a pane's borders
+    let path_room = (sides[0].width as usize).saturating_sub(2);
 
-    f.render_widget(content, area);
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+    let old_fields = change.old.as_ref().map(|e| metadata_fields(&e.track_mode));
+    let new_fields = change.new.as_ref().map(|e| metadata_fields(&e.track_mode));
+    let split = browse::split_lines(change);
+
+    let side_lines = |fields: &Option<[(&'static str, String); 6]>,
+                      other: &Option<[(&'static str, String); 6]>,
+                      color: Color,
+                      old_side: bool| {
+        let mut lines = vec![match fields {
+            Some(_) => Line::from(Span::styled(ellipsize_middle(&change.path, path_room), value_style)),
+            None => Line::from(Span::styled("Not in this baseline", label_style)),
+        }];
+        for i in 0..6 {
+            let Some((label, value)) = fields.as_ref().map(|f| &f[i]) else {
+                lines.push(Line::from(""));
+                continue;
+            };
+            let differs = other.as_ref().is_some_and(|o| o[i].1 != *value);
+            let style = if differs { Style::default().fg(dimming.dim_color(color)) } else { value_style };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<9} ", format!("{}:", label)), label_style),
+                Span::styled(value.clone(), style),
+            ]));
+        }
+        lines.push(Line::from(""));
+        match &split {
+            Some(rows) => {
+                for row in rows {
+                    let side = if old_side { &row.old } else { &row.new };
+                    let style = if row.is_change() { Style::default().fg(dimming.dim_color(color)) } else { value_style };
+                    lines.push(match side {
+                        Some((number, text)) => Line::from(vec![
+                            Span::styled(format!("{:>4} ", number), label_style),
+                            Span::styled(text.clone(), style),
+                        ]),
+                        None => Line::from(Span::styled("   ~", label_style)),
+                    });
+                }
+            }
+            None => lines.push(Line::from(Span::styled("Content not tracked", label_style))),
+        }
+        lines
+    };
+
+    for (area, title, lines) in [
+        (sides[0], format!("─ A: {} ─", from), side_lines(&old_fields, &new_fields, Color::Red, true)),
+        (sides[1], format!("─ B: {} ─", to), side_lines(&new_fields, &old_fields, Color::Green, false)),
+    ] {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_alignment(ratatui::layout::Alignment::Left)
+            .border_style(Style::default().fg(dimming.border_color(true)));
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .scroll((app.change_detail_scroll, 0));
+        f.render_widget(paragraph, area);
+    }
 }
 
+/// Metadata deltas, security findings and content diff of the selected change
+fn render_change_details(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("─ Details ─")
+        .title_alignment(ratatui::layout::Alignment::Left)
+        .border_style(Style::default().fg(dimming.border_color(true)));
+
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let lines = match (app.selected_file_change(), app.change_rows.get(app.selected_change)) {
+        (Some(change), _) => change_detail_lines(change, app, dimming),
+        (None, Some(Row::Dir { path, count, .. })) => vec![
+            Line::from(vec![Span::styled("Directory: ", label_style), Span::styled(path.clone(), value_style)]),
+            Line::from(vec![Span::styled("Changes:   ", label_style), Span::styled(count.to_string(), value_style)]),
+            Line::from(""),
+            Line::from(Span::styled("[Enter] to fold or unfold", label_style)),
+        ],
+        _ => Vec::new(),
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.change_detail_scroll, 0));
+
+    f.render_widget(paragraph, area);
+}
+
+pub(crate) fn change_detail_lines(change: &FileChange, app: &App, dimming: &DimmingContext) -> Vec<Line<'static>> {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let kind = match change.kind {
+        ChangeKind::Added => "new",
+        ChangeKind::Removed => "deleted",
+        ChangeKind::Modified if change.is_metadata_only() => "modified (metadata only)",
+        ChangeKind::Modified => "modified",
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Path:    ", label_style), Span::styled(change.path.clone(), value_style)]),
+        Line::from(vec![
+            Span::styled("Change:  ", label_style),
+            Span::styled(kind, Style::default().fg(dimming.dim_color(kind_color(change.kind)))),
+        ]),
+        Line::from(vec![
+            Span::styled("Package: ", label_style),
+            Span::styled(app.packages.owner(&change.path).unwrap_or("-").to_string(), value_style),
+        ]),
+    ];
+
+    let findings = security::assess(change);
+    if !findings.is_empty() {
+        lines.push(Line::from(""));
+        for finding in findings {
+            lines.push(Line::from(vec![
+                severity_span(Some(finding.severity), dimming),
+                Span::styled(finding.message, value_style),
+            ]));
+        }
+    }
+
+    // Metadata, old -> new where it changed
+    lines.push(Line::from(""));
+    let old = change.old.as_ref().map(|e| metadata_fields(&e.track_mode));
+    let new = change.new.as_ref().map(|e| metadata_fields(&e.track_mode));
+    for i in 0..6 {
+        let old_field = old.as_ref().map(|fields| &fields[i]);
+        let new_field = new.as_ref().map(|fields| &fields[i]);
+        let Some((label, _)) = new_field.or(old_field) else { break };
+        let mut spans = vec![Span::styled(format!("{:<9} ", format!("{}:", label)), label_style)];
+        match (old_field, new_field) {
+            (Some((_, old_value)), Some((_, new_value))) if old_value != new_value => {
+                spans.push(Span::styled(old_value.clone(), Style::default().fg(dimming.dim_color(Color::Red))));
+                spans.push(Span::styled(" -> ", label_style));
+                spans.push(Span::styled(new_value.clone(), Style::default().fg(dimming.dim_color(Color::Green))));
+            }
+            (_, Some((_, value))) | (Some((_, value)), None) => spans.push(Span::styled(value.clone(), value_style)),
+            (None, None) => {}
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(diff) = crate::report::content_diff(change) {
+        lines.push(Line::from(""));
+        for line in diff.lines() {
+            let color = if line.starts_with("@@") {
+                Color::Cyan
+            } else if line.starts_with('+') {
+                Color::Green
+            } else if line.starts_with('-') {
+                Color::Red
+            } else {
+                Color::Reset
+            };
+            let style = if color == Color::Reset { value_style } else { Style::default().fg(dimming.dim_color(color)) };
+            lines.push(Line::from(Span::styled(line.to_string(), style)));
+        }
+    }
+    lines
+}
+
+/// Labelled metadata of one side of a change, in display order
+fn metadata_fields(mode: &TrackMode) -> [(&'static str, String); 6] {
+    [
+        ("Size", mode.size().to_string()),
+        ("Modified", format_time(mode.modified())),
+        ("Mode", format!("{:04o}", mode.permissions() & 0o7777)),
+        ("Owner", mode.owner().to_string()),
+        ("Group", mode.group().to_string()),
+        // Enough of the digest to tell them apart without wrapping
+        ("Checksum", mode.checksum().map(|c| c.chars().take(16).collect()).unwrap_or_else(|| "-".to_string())),
+    ]
+}
+
+fn kind_color(kind: ChangeKind) -> Color {
+    match kind {
+        ChangeKind::Added => Color::Green,
+        ChangeKind::Modified => Color::Yellow,
+        ChangeKind::Removed => Color::Red,
+    }
+}
+
+fn format_time(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Fixed-width severity tag so paths stay aligned
+fn severity_span(severity: Option<Severity>, dimming: &DimmingContext) -> Span<'static> {
+    let (text, color) = match severity {
+        Some(Severity::High) => ("HIGH ", Color::Red),
+        Some(Severity::Medium) => ("MED  ", Color::Yellow),
+        Some(Severity::Low) => ("LOW  ", Color::Cyan),
+        None => ("     ", Color::Reset),
+    };
+    Span::styled(text, Style::default().fg(dimming.dim_color(color)).add_modifier(Modifier::BOLD))
+}
+
+fn binding_lines(dimming: &DimmingContext) -> Vec<Line<'static>> {
+    let key = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD));
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(false)));
+
+    // [↑↓] Select | [Enter] Fold dir | [v] Tree/flat | [b] Side by side | [[ ]] Scroll details | [l] Compare live | [x] Export report
+    // [t] Type | [s] Severity | [g] Path | [o] Package | [⌫] Clear filters
+    vec![
+        Line::from(vec![
+            key("[↑↓] "), label("Select"), label(" | "),
+            key("[Enter] "), label("Fold dir"), label(" | "),
+            key("[v] "), label("Tree/flat"), label(" | "),
+            key("[b] "), label("Side by side"), label(" | "),
+            key("[[ ]] "), label("Scroll details"), label(" | "),
+            key("[l] "), label("Compare live"), label(" | "),
+            key("[x] "), label("Export report"),
+        ]),
+        Line::from(vec![
+            label("Filter: "),
+            key("[t] "), label("Type"), label(" | "),
+            key("[s] "), label("Severity"), label(" | "),
+            key("[g] "), label("Path"), label(" | "),
+            key("[o] "), label("Package"), label(" | "),
+            key("[⌫] "), label("Clear filters"),
+        ]),
+    ]
+}
@@ -1,23 +1,271 @@
 // Dashboard view
 use crate::app::App;
-use tui_components::DimmingContext;
+use crate::compare::ChangeKind;
+use crate::summary::ago;
+use crate::util::format_size;
+use tui_components::{ellipsize_middle, DimmingContext};
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    text::Line,
-    widgets::{Block, Borders, Paragraph},
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use std::time::SystemTime;
 
-pub fn render_dashboard(f: &mut Frame, area: Rect, _app: &App, _dimming: &DimmingContext) {
+pub fn render_dashboard(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    // The outer frame carries the tab bar, so the cards' titles stay visible
     let block = Block::default()
         .borders(Borders::ALL)
         .title(" Dashboard ")
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let area = {
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        inner
+    };
 
-    let content = Paragraph::new(Line::from("Dashboard view - Coming soon"))
-        .block(block);
+    // Narrow screens stack the cards and the lists
+    let narrow = super::is_narrow(area);
+    let (bindings, bindings_height) = super::bindings_box(binding_lines(dimming), area.width, dimming);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if narrow { 16 } else { 6 }), // Cards
+            Constraint::Min(5),                                // Recent changes and busiest directories
+            Constraint::Length(bindings_height),               // Bindings box
+        ])
+        .split(area);
 
-    f.render_widget(content, area);
+    let cards = if narrow {
+        Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Length(6), // Room for a refresh in progress
+                Constraint::Length(5),
+            ])
+            .split(chunks[0])
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+                Constraint::Ratio(1, 3),
+            ])
+            .split(chunks[0])
+    };
+    render_baseline_card(f, cards[0], app, dimming);
+    render_drift_card(f, cards[1], app, dimming);
+    render_store_card(f, cards[2], app, dimming);
+
+    let lists = Layout::default()
+        .direction(if narrow { Direction::Vertical } else { Direction::Horizontal })
+        .constraints([
+            Constraint::Percentage(60),
+            Constraint::Percentage(40),
+        ])
+        .split(chunks[1]);
+    render_recent_changes(f, lists[0], app, dimming);
+    render_top_dirs(f, lists[1], app, dimming);
+
+    f.render_widget(bindings, chunks[2]);
+}
+
+fn card<'a>(title: &'a str, lines: Vec<Line<'a>>, dimming: &DimmingContext) -> Paragraph<'a> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", title))
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    Paragraph::new(lines).block(block)
 }
 
+fn render_baseline_card(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let lines = match app.baselines.get(app.active_baseline) {
+        Some(baseline) => vec![
+            Line::from(Span::styled(
+                match &baseline.label {
+                    Some(label) => format!("{} ({})", baseline.version, label),
+                    None => baseline.version.clone(),
+                },
+                value_style.add_modifier(Modifier::BOLD),
+            )),
+            Line::from(vec![
+                Span::styled("Created: ", label_style),
+                Span::styled(ago(seconds_since(baseline.created_at)), value_style),
+            ]),
+            Line::from(vec![
+                Span::styled("Host:    ", label_style),
+                Span::styled(baseline.host.clone(), value_style),
+            ]),
+        ],
+        None => vec![Line::from(Span::styled("No baselines yet - press [3] then [i]", label_style))],
+    };
+    f.render_widget(card("Active Baseline", lines, dimming), area);
+}
+
+fn render_drift_card(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let mut lines = Vec::new();
+    match &app.drift_summary {
+        Some(summary) => {
+            let color = if summary.total() == 0 { Color::Green } else { Color::Yellow };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{} changes", summary.total()),
+                    Style::default().fg(dimming.dim_color(color)).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  +{} ~{} -{}", summary.added, summary.modified, summary.removed),
+                    value_style,
+                ),
+            ]));
+            let active = app.baselines.get(app.active_baseline).map(|b| b.filename.as_str());
+            let stale = if active == Some(summary.baseline.as_str()) { "" } else { " (not active)" };
+            lines.push(Line::from(vec![
+                Span::styled("Against:   ", label_style),
+                Span::styled(format!("{}{}", summary.against, stale), value_style),
+            ]));
+            let scanned = summary.scanned_at.elapsed().unwrap_or_default().as_secs();
+            lines.push(Line::from(vec![
+                Span::styled("Last scan: ", label_style),
+                Span::styled(
+                    format!("{} (took {}s)", ago(scanned), summary.scan_duration.as_secs()),
+                    value_style,
+                ),
+            ]));
+        }
+        None if app.dashboard_refresh.is_none() && app.live_compare.is_none() && app.dashboard_error.is_none() => {
+            lines.push(Line::from(Span::styled("Not scanned yet - press [r]", label_style)));
+        }
+        None => {}
+    }
+    if let Some(refresh) = app.dashboard_refresh.as_ref().or(app.live_compare.as_ref()) {
+        let running = refresh.started_at.elapsed().unwrap_or_default().as_secs();
+        lines.push(Line::from(Span::styled(
+            format!("Scanning the live system... {}s", running),
+            Style::default().fg(dimming.dim_color(Color::Cyan)),
+        )));
+    }
+    if let Some(error) = &app.dashboard_error {
+        lines.push(Line::from(Span::styled(
+            format!("Refresh failed: {}", error),
+            Style::default().fg(dimming.dim_color(Color::Red)),
+        )));
+    }
+    f.render_widget(card("Drift Since Active Baseline", lines, dimming), area);
+}
+
+fn render_store_card(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let store_size: u64 = app.baselines.iter().map(|b| b.size_bytes).sum();
+    let watcher = if app.config_watcher.is_some() { "watching config" } else { "unavailable" };
+    let refresh = match (app.config.dashboard.refresh_minutes, app.next_dashboard_refresh) {
+        (0, _) => "manual".to_string(),
+        (minutes, Some(next)) => {
+            let due = next.duration_since(SystemTime::now()).unwrap_or_default().as_secs();
+            format!("every {}m, next in {}m", minutes, due.div_ceil(60))
+        }
+        (minutes, None) => format!("every {}m", minutes),
+    };
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Baselines: ", label_style),
+            Span::styled(format!("{} ({})", app.baselines.len(), format_size(store_size)), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Watcher:   ", label_style),
+            Span::styled(watcher, value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Refresh:   ", label_style),
+            Span::styled(refresh, value_style),
+        ]),
+    ];
+    f.render_widget(card("Store & Monitoring", lines, dimming), area);
+}
+
+fn render_recent_changes(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Recently Changed Files ")
+        .border_style(Style::default().fg(dimming.border_color(true)));
+
+    // Kind and age take 14 columns inside the borders
+    let room = (area.width as usize).saturating_sub(16);
+    let items: Vec<ListItem> = match &app.drift_summary {
+        Some(summary) if !summary.recent.is_empty() => summary.recent.iter()
+            .map(|change| {
+                let color = match change.kind {
+                    ChangeKind::Added => Color::Green,
+                    ChangeKind::Modified => Color::Yellow,
+                    ChangeKind::Removed => Color::Red,
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", change.kind.symbol()),
+                        Style::default().fg(dimming.dim_color(color)).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{:>9}  ", ago(seconds_since(change.modified))), Style::default().fg(dimming.text_color(false))),
+                    Span::styled(ellipsize_middle(&change.path, room), Style::default().fg(dimming.text_color(true))),
+                ]))
+            })
+            .collect(),
+        Some(_) => vec![ListItem::new("No changes since the baseline")],
+        None => Vec::new(),
+    };
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn render_top_dirs(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Top Directories by Churn ")
+        .border_style(Style::default().fg(dimming.border_color(true)));
+
+    let room = (area.width as usize).saturating_sub(10);
+    let items: Vec<ListItem> = app.drift_summary.iter()
+        .flat_map(|summary| &summary.top_dirs)
+        .map(|(dir, count)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>6}  ", count), Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+                Span::styled(ellipsize_middle(dir, room), Style::default().fg(dimming.text_color(true))),
+            ]))
+        })
+        .collect();
+    f.render_widget(List::new(items).block(block), area);
+}
+
+fn binding_lines(dimming: &DimmingContext) -> Vec<Line<'static>> {
+    // Bindings text: [r] Refresh now | [2] Changes | [3] Baselines
+    vec![
+        Line::from(vec![
+            Span::styled("[r] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Refresh now", Style::default().fg(dimming.text_color(false))),
+            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
+            Span::styled("[2] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Changes", Style::default().fg(dimming.text_color(false))),
+            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
+            Span::styled("[3] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Baselines", Style::default().fg(dimming.text_color(false))),
+        ]),
+    ]
+}
+
+/// Seconds from a Unix timestamp until now (0 for timestamps in the future)
+fn seconds_since(timestamp: u64) -> u64 {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    now.saturating_sub(timestamp)
+}
@@ -0,0 +1,145 @@
+// File history overlay
+use crate::app::{App, Hit, Pane};
+use crate::compare::ChangeKind;
+use crate::views::changes::change_detail_lines;
+use tui_components::{centered_rect, ellipsize_middle, DimmingContext};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render_history(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let Some(history) = &app.history else {
+        return;
+    };
+
+    // Narrow screens give the history all of it, steps above the details
+    let narrow = super::is_narrow(area);
+    let area = if narrow { area } else { centered_rect(90, 80, area) };
+    f.render_widget(Clear, area);
+
+    let counts = format!(" ({} changes) ", history.steps.len());
+    // Borders and " History: " take 12 columns
+    let room = (area.width as usize).saturating_sub(12 + counts.len());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" History: {}{}", ellipsize_middle(&history.path, room), counts))
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let label_style = Style::default().fg(dimming.text_color(false));
+    let key = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD));
+    let label = |text: &'static str| Span::styled(text, label_style);
+    // [↑↓] Select | [[ ]] Scroll | [Enter] Open in baseline | [Esc] Close
+    let bindings = tui_components::wrap_bindings(vec![Line::from(vec![
+        key("[↑↓] "), label("Select"), label(" | "),
+        key("[[ ]] "), label("Scroll"), label(" | "),
+        key("[Enter] "), label("Open in baseline"), label(" | "),
+        key("[Esc] "), label("Close"),
+    ])], inner.width);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                        // Steps and details
+            Constraint::Length(bindings.len() as u16), // Bindings
+        ])
+        .split(inner);
+    let panes = Layout::default()
+        .direction(if narrow { ratatui::layout::Direction::Vertical } else { ratatui::layout::Direction::Horizontal })
+        .constraints([
+            Constraint::Percentage(40), // Steps
+            Constraint::Percentage(60), // Details of the selected step
+        ])
+        .split(chunks[0]);
+
+
+    let value_style = Style::default().fg(dimming.text_color(true));
+
+    let items: Vec<ListItem> = history.steps.iter()
+        .map(|step| {
+            let (kind, color) = match step.change.kind {
+                ChangeKind::Added => ("added   ", Color::Green),
+                ChangeKind::Modified => ("modified", Color::Yellow),
+                ChangeKind::Removed => ("removed ", Color::Red),
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {}  ", kind), Style::default().fg(dimming.dim_color(color))),
+                Span::styled(step.version.clone(), value_style),
+            ];
+            if !step.packages.is_empty() {
+                spans.push(Span::styled(" [pkg]", Style::default().fg(dimming.dim_color(Color::Magenta))));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let steps_block = Block::default()
+        .borders(Borders::ALL)
+        .title("─ Baselines ─")
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let rows_area = steps_block.inner(panes[0]);
+    let list = List::new(items)
+        .block(steps_block)
+        .highlight_style(dimming.selection_style(true));
+    let mut state = ListState::default();
+    state.select(Some(history.selected));
+    f.render_stateful_widget(list, panes[0], &mut state);
+    app.hits.push(panes[0], Hit::Pane(Pane::HistorySteps));
+    app.hits.push_list(rows_area, state.offset(), history.steps.len(), |i| Some(Hit::Row(Pane::HistorySteps, i)));
+
+    // Who or what changed it, then the change itself
+    let mut lines = Vec::new();
+    if let Some(step) = history.selected_step() {
+        lines.push(Line::from(vec![
+            Span::styled("Baseline: ", label_style),
+            Span::styled(format!("{} ({})", step.version, format_time(step.created_at)), value_style),
+        ]));
+        for event in &step.packages {
+            lines.push(Line::from(vec![
+                Span::styled("dpkg:     ", label_style),
+                Span::styled(
+                    format!("{} {} {} at {}", event.action, event.package, event.version, format_time(event.at)),
+                    Style::default().fg(dimming.dim_color(Color::Magenta)),
+                ),
+            ]));
+        }
+        if let Some(seen) = step.first_seen {
+            lines.push(Line::from(vec![
+                Span::styled("Seen:     ", label_style),
+                Span::styled(format!("first by a live scan at {}", format_time(seen)), value_style),
+            ]));
+        }
+        if step.packages.is_empty() && step.first_seen.is_none() {
+            let note = match &history.owner {
+                Some(owner) => format!("no {} package activity recorded since the previous baseline", owner),
+                None => "no package owns this file and no live scan saw the change".to_string(),
+            };
+            lines.push(Line::from(vec![Span::styled("Cause:    ", label_style), Span::styled(note, value_style)]));
+        }
+        lines.push(Line::from(""));
+        lines.extend(change_detail_lines(&step.change, app, dimming));
+    }
+    let details = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("─ Change ─")
+                .border_style(Style::default().fg(dimming.border_color(true))),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((history.scroll, 0));
+    f.render_widget(details, panes[1]);
+    app.hits.push(panes[1], Hit::Pane(Pane::HistoryDetails));
+
+    f.render_widget(Paragraph::new(bindings), chunks[1]);
+}
+
+fn format_time(secs: u64) -> String {
+    crate::timeline::local_time(secs)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -2,10 +2,44 @@
 pub mod dashboard;
 pub mod changes;
 pub mod baselines;
+pub mod timeline;
+pub mod search;
+pub mod history;
 pub mod popup;
 
 pub use dashboard::render_dashboard;
 pub use changes::render_changes;
 pub use baselines::render_baselines;
+pub use timeline::render_timeline;
+pub use search::render_search;
+pub use history::render_history;
 pub use popup::render_popup;
 
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+use tui_components::DimmingContext;
+
+/// Views narrower than this (e.g. a phone's SSH client) stack their panes
+/// in a single column
+pub const NARROW_WIDTH: u16 = 80;
+
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// The "─ Bindings ─" box for `lines` wrapped to fit `width` columns, and
+/// the height it needs
+pub(crate) fn bindings_box(lines: Vec<Line<'static>>, width: u16, dimming: &DimmingContext) -> (Paragraph<'static>, u16) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("─ Bindings ─")
+        .title_alignment(ratatui::layout::Alignment::Left)
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let lines = tui_components::wrap_bindings(lines, width.saturating_sub(2));
+    let height = lines.len() as u16 + 2;
+    (Paragraph::new(lines).block(block), height)
+}
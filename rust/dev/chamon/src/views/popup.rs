@@ -2,9 +2,11 @@
 use crate::app::App;
 use tui_components::render_popup as render_popup_component;
 
-pub fn render_popup(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    if let Some(popup) = &app.popup {
-        render_popup_component(f, area, popup);
+/// Render the open popup, if any, and return the screen area of its buttons
+pub fn render_popup(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) -> Vec<ratatui::layout::Rect> {
+    match &app.popup {
+        Some(popup) => render_popup_component(f, area, popup),
+        None => Vec::new(),
     }
 }
 
@@ -0,0 +1,129 @@
+// Search results overlay
+use crate::app::{App, Hit, Pane};
+use crate::search::{HitSource, SearchHit, SearchMode};
+use tui_components::{centered_rect, ellipsis_range, ellipsize_middle, DimmingContext};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn render_search(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let Some(search) = &app.search else {
+        return;
+    };
+
+    // Narrow screens give the results all of it
+    let area = if super::is_narrow(area) { area } else { centered_rect(80, 70, area) };
+    f.render_widget(Clear, area);
+
+    let more = if search.truncated { "+" } else { "" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " Search {}: \"{}\" ({}{} results) ",
+            search.mode.label(),
+            search.query,
+            search.hits.len(),
+            more,
+        ))
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let key = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD));
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(dimming.text_color(false)));
+    // [↑↓] Select | [Enter] Open | [/] Search paths | [?] Search contents | [Esc] Close
+    let bindings = tui_components::wrap_bindings(vec![Line::from(vec![
+        key("[↑↓] "), label("Select"), label(" | "),
+        key("[Enter] "), label("Open"), label(" | "),
+        key("[/] "), label("Search paths"), label(" | "),
+        key("[?] "), label("Search contents"), label(" | "),
+        key("[Esc] "), label("Close"),
+    ])], inner.width);
+
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                        // Results
+            Constraint::Length(bindings.len() as u16), // Bindings
+        ])
+        .split(inner);
+
+    // Columns left for the path after the source tag
+    let room = (inner.width as usize).saturating_sub(19);
+    let items: Vec<ListItem> = search.hits.iter()
+        .map(|hit| match search.mode {
+            SearchMode::Paths => path_item(hit, room, dimming),
+            SearchMode::Contents => content_item(hit, room, dimming),
+        })
+        .collect();
+    let list = List::new(items).highlight_style(dimming.selection_style(true));
+    let mut state = ListState::default();
+    state.select(Some(search.selected));
+    f.render_stateful_widget(list, chunks[0], &mut state);
+    app.hits.push(chunks[0], Hit::Pane(Pane::SearchResults));
+    app.hits.push_list(chunks[0], state.offset(), search.hits.len(), |i| Some(Hit::Row(Pane::SearchResults, i)));
+
+    f.render_widget(Paragraph::new(bindings), chunks[1]);
+}
+
+/// Where the hit lives, as a fixed-width tag
+fn source_span(source: &HitSource, dimming: &DimmingContext) -> Span<'static> {
+    match source {
+        HitSource::Change => Span::styled(
+            format!("{:<18}", "change"),
+            Style::default().fg(dimming.dim_color(Color::Yellow)),
+        ),
+        HitSource::Baseline { version, .. } => Span::styled(
+            format!("{:<18}", version),
+            Style::default().fg(dimming.dim_color(Color::Cyan)),
+        ),
+    }
+}
+
+/// The path with the fuzzy-matched characters highlighted, shortened from
+/// the middle to `width` columns
+fn path_item(hit: &SearchHit, width: usize, dimming: &DimmingContext) -> ListItem<'static> {
+    let plain = Style::default().fg(dimming.text_color(true));
+    let matched = Style::default().fg(dimming.dim_color(Color::Green)).add_modifier(Modifier::BOLD);
+
+    let mut spans = vec![Span::raw(" "), source_span(&hit.source, dimming)];
+    let cut = ellipsis_range(&hit.path, width).unwrap_or_default();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (pos, c) in hit.path.chars().enumerate() {
+        if pos == cut.start && !cut.is_empty() {
+            if !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), if run_matched { matched } else { plain }));
+            }
+            spans.push(Span::styled("…", plain));
+        }
+        if cut.contains(&pos) {
+            continue;
+        }
+        let is_match = hit.positions.binary_search(&pos).is_ok();
+        if is_match != run_matched && !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut run), if run_matched { matched } else { plain }));
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    spans.push(Span::styled(run, if run_matched { matched } else { plain }));
+    ListItem::new(Line::from(spans))
+}
+
+/// Baseline, path and line number, then the matching line. The path gets
+/// at most two thirds of `width` so some of the line stays visible.
+fn content_item(hit: &SearchHit, width: usize, dimming: &DimmingContext) -> ListItem<'static> {
+    let (number, text) = hit.line.clone().unwrap_or_default();
+    let path = ellipsize_middle(&hit.path, width * 2 / 3);
+    ListItem::new(Line::from(vec![
+        Span::raw(" "),
+        source_span(&hit.source, dimming),
+        Span::styled(format!("{}:{}", path, number), Style::default().fg(dimming.text_color(true))),
+        Span::styled(format!("  {}", text), Style::default().fg(dimming.text_color(false))),
+    ]))
+}
@@ -0,0 +1,151 @@
+// Timeline view
+use crate::app::{App, Hit, Pane};
+use crate::compare::ChangeKind;
+use crate::timeline::{self, EventKind, Row};
+use tui_components::{ellipsize_middle, DimmingContext};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn render_timeline(f: &mut Frame, area: Rect, app: &App, dimming: &DimmingContext) {
+    let (bindings, bindings_height) = super::bindings_box(binding_lines(dimming), area.width, dimming);
+    let chunks = Layout::default()
+        .direction(ratatui::layout::Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                  // Timeline
+            Constraint::Length(3),               // Statistics
+            Constraint::Length(bindings_height), // Bindings box
+        ])
+        .split(area);
+
+    let now = chrono::Local::now();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Timeline ({} events) ", app.timeline.len()))
+        .border_style(Style::default().fg(dimming.border_color(true)));
+    let rows_area = block.inner(chunks[0]);
+
+    let rows = timeline::rows(&app.timeline, now.date_naive());
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "Nothing recorded yet - baselines, Dashboard refreshes and `chamon check` runs fill the timeline",
+            Style::default().fg(dimming.text_color(false)),
+        ))]
+    } else {
+        rows.iter()
+            .map(|row| match row {
+                Row::Day(label) => ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", label),
+                        Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled("─".repeat(40), Style::default().fg(dimming.border_color(false))),
+                ])),
+                Row::Event(index) => event_item(&app.timeline[*index], rows_area.width, dimming),
+            })
+            .collect()
+    };
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut state = ListState::default();
+    state.select(rows.iter().position(|r| *r == Row::Event(app.selected_event)));
+    f.render_stateful_widget(list, chunks[0], &mut state);
+    app.hits.push(chunks[0], Hit::Pane(Pane::Timeline));
+    // Day headers can't be selected
+    app.hits.push_list(rows_area, state.offset(), rows.len(), |i| match &rows[i] {
+        Row::Event(index) => Some(Hit::Row(Pane::Timeline, *index)),
+        Row::Day(_) => None,
+    });
+
+    render_statistics(f, chunks[1], app, now, dimming);
+    f.render_widget(bindings, chunks[2]);
+}
+
+/// One event as a row `width` columns wide; long paths lose their middle
+fn event_item(event: &timeline::Event, width: u16, dimming: &DimmingContext) -> ListItem<'static> {
+    let time = timeline::local_time(event.at)
+        .map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_else(|| "--:--".to_string());
+
+    let (marker, color, title, detail) = match &event.kind {
+        EventKind::Baseline { version, initial, file_count, .. } => (
+            "◆",
+            Color::Cyan,
+            if *initial { "Initial baseline created" } else { "Baseline created" },
+            if *initial {
+                format!("{} files", file_count)
+            } else {
+                format!("{} ({} changes)", version, file_count)
+            },
+        ),
+        EventKind::File { path, change } => {
+            let (title, color) = match change {
+                ChangeKind::Added => ("New file", Color::Green),
+                ChangeKind::Modified => ("File modified", Color::Yellow),
+                ChangeKind::Removed => ("File deleted", Color::Red),
+            };
+            // Time, marker and title take 36 columns
+            ("●", color, title, ellipsize_middle(path, (width as usize).saturating_sub(36)))
+        }
+        EventKind::Package { action, package, version } => {
+            let title = match action.as_str() {
+                "install" => "Package installed",
+                "upgrade" => "Package upgraded",
+                _ => "Package removed",
+            };
+            ("■", Color::Magenta, title, format!("{} {}", package, version))
+        }
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("  {}  ", time), Style::default().fg(dimming.text_color(false))),
+        Span::styled(format!("{} ", marker), Style::default().fg(dimming.dim_color(color))),
+        Span::styled(format!("{:<25}", title), Style::default().fg(dimming.text_color(true))),
+        Span::styled(detail, Style::default().fg(dimming.text_color(false))),
+    ]))
+}
+
+fn render_statistics(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    now: chrono::DateTime<chrono::Local>,
+    dimming: &DimmingContext,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Statistics ")
+        .border_style(Style::default().fg(dimming.border_color(true)));
+
+    let stats = timeline::stats(&app.timeline, now);
+    let text = format!(
+        "File changes today: {} | This week: {} | This month: {}",
+        stats.today, stats.week, stats.month,
+    );
+    let paragraph = Paragraph::new(Line::from(Span::styled(text, Style::default().fg(dimming.text_color(true)))))
+        .block(block);
+    f.render_widget(paragraph, area);
+}
+
+fn binding_lines(dimming: &DimmingContext) -> Vec<Line<'static>> {
+    // Bindings text: [↑↓] Select | [Enter] Compare baseline / Diff file / Filter by package | [r] Reload
+    vec![
+        Line::from(vec![
+            Span::styled("[↑↓] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Select", Style::default().fg(dimming.text_color(false))),
+            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
+            Span::styled("[Enter] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Compare baseline / Diff file / Filter by package", Style::default().fg(dimming.text_color(false))),
+            Span::styled(" | ", Style::default().fg(dimming.text_color(false))),
+            Span::styled("[r] ", Style::default().fg(dimming.text_color(true)).add_modifier(Modifier::BOLD)),
+            Span::styled("Reload", Style::default().fg(dimming.text_color(false))),
+        ]),
+    ]
+}
@@ -190,7 +190,7 @@ pub fn render_global_bindings(
         ));
     }
 
-    let bindings_text = vec![Line::from(spans)];
+    let bindings_text = wrap_bindings(vec![Line::from(spans)], area.width.saturating_sub(2));
     let paragraph = Paragraph::new(bindings_text)
         .block(block)
         .alignment(ratatui::layout::Alignment::Left);
@@ -198,3 +198,48 @@ pub fn render_global_bindings(
     f.render_widget(paragraph, area);
 }
 
+/// Wrap binding lines to `width` columns, breaking only between bindings
+/// (at their " | " separators) so a key never ends up apart from its label.
+/// Lines that fit are kept as they are; a binding wider than `width` gets a
+/// line of its own and is cut off when drawn.
+pub fn wrap_bindings(lines: Vec<Line<'static>>, width: u16) -> Vec<Line<'static>> {
+    let width = width as usize;
+    let mut wrapped = Vec::new();
+    for line in lines {
+        if line.width() <= width {
+            wrapped.push(line);
+            continue;
+        }
+        
+        // Bindings, each with the separator that came before it
+        let mut bindings: Vec<(Option<Span<'static>>, Vec<Span<'static>>)> = vec![(None, Vec::new())];
+        for span in line.spans {
+            if span.content == " | " {
+                bindings.push((Some(span), Vec::new()));
+            } else if let Some((_, spans)) = bindings.last_mut() {
+                spans.push(span);
+            }
+        }
+        
+        let mut current: Vec<Span<'static>> = Vec::new();
+        let mut current_width = 0;
+        for (separator, spans) in bindings {
+            let binding_width: usize = spans.iter().map(|s| s.width()).sum();
+            let separator_width = separator.as_ref().map_or(0, |s| s.width());
+            if !current.is_empty() && current_width + separator_width + binding_width > width {
+                wrapped.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+            }
+            if let Some(separator) = separator.filter(|_| !current.is_empty()) {
+                current_width += separator.width();
+                current.push(separator);
+            }
+            current_width += binding_width;
+            current.extend(spans);
+        }
+        if !current.is_empty() {
+            wrapped.push(Line::from(current));
+        }
+    }
+    wrapped
+}
@@ -196,3 +196,73 @@ pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
     
     lines
 }
+
+/// Char range of `text` to replace with "…" so it fits in `max_width`
+/// columns, or None when it already fits. A path keeps its file name whole
+/// when that leaves room for some of its start; otherwise the middle goes.
+pub fn ellipsis_range(text: &str, max_width: usize) -> Option<std::ops::Range<usize>> {
+    let len = text.chars().count();
+    if len <= max_width {
+        return None;
+    }
+    
+    // One column goes to the ellipsis
+    let budget = max_width.saturating_sub(1);
+    let name_len = text.chars().rev().position(|c| c == '/').map_or(0, |pos| pos + 1);
+    let tail = if name_len > 0 && name_len < budget {
+        name_len.max(budget / 2)
+    } else {
+        budget - budget / 2
+    };
+    let head = budget - tail;
+    Some(head..len - tail)
+}
+
+/// Shorten `text` to `max_width` columns by replacing its middle with "…",
+/// e.g. "/etc/ssh/sshd_config" at 18 columns becomes "/etc/…/sshd_config"
+pub fn ellipsize_middle(text: &str, max_width: usize) -> String {
+    let Some(cut) = ellipsis_range(text, max_width) else {
+        return text.to_string();
+    };
+    if max_width == 0 {
+        return String::new();
+    }
+    
+    let mut shortened = String::new();
+    for (pos, c) in text.chars().enumerate() {
+        if pos == cut.start {
+            shortened.push('…');
+        }
+        if !cut.contains(&pos) {
+            shortened.push(c);
+        }
+    }
+    shortened
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn ellipsize_keeps_text_that_fits() {
+        assert_eq!(ellipsize_middle("/etc/hosts", 10), "/etc/hosts");
+        assert_eq!(ellipsize_middle("/etc/hosts", 40), "/etc/hosts");
+    }
+    
+    #[test]
+    fn ellipsize_keeps_the_file_name_when_it_can() {
+        assert_eq!(ellipsize_middle("/etc/ssh/sshd_config", 18), "/etc/…/sshd_config");
+        // Too long a name for that: the middle goes instead
+        assert_eq!(ellipsize_middle("/etc/ssh/sshd_config", 10), "/etc…onfig");
+        assert_eq!(ellipsize_middle("averyveryverylongname", 9), "aver…name");
+    }
+    
+    #[test]
+    fn ellipsize_counts_chars_and_handles_tiny_widths() {
+        assert_eq!(ellipsize_middle("ääääää", 4), "ä…ää");
+        assert_eq!(ellipsize_middle("/a/b", 3), "/…b");
+        assert_eq!(ellipsize_middle("/a/b", 1), "…");
+        assert_eq!(ellipsize_middle("x/y", 0), "");
+    }
+}
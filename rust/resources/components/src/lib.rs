@@ -6,6 +6,7 @@ pub mod file_browser;
 pub mod form_panel;
 pub mod helpers;
 pub mod list_panel;
+pub mod mouse;
 pub mod popup;
 pub mod tab_bar;
 pub mod toast;
@@ -15,6 +16,7 @@ pub use file_browser::*;
 pub use form_panel::*;
 pub use helpers::*;
 pub use list_panel::*;
+pub use mouse::*;
 pub use popup::*;
 pub use tab_bar::*;
 pub use toast::*;
@@ -0,0 +1,92 @@
+// Mouse hit-testing
+// Apps record what sits where while rendering, then look up mouse events in it
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use std::cell::RefCell;
+
+/// What a mouse event asks for, independent of where it happened
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseAction {
+    /// Left button pressed
+    Click,
+    ScrollUp,
+    ScrollDown,
+}
+
+impl MouseAction {
+    /// Classify a crossterm mouse event; moves, drags and releases are `None`
+    pub fn from_event(event: &MouseEvent) -> Option<Self> {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(MouseAction::Click),
+            MouseEventKind::ScrollUp => Some(MouseAction::ScrollUp),
+            MouseEventKind::ScrollDown => Some(MouseAction::ScrollDown),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the cell at `column`, `row` lies inside `rect`
+pub fn rect_contains(rect: Rect, column: u16, row: u16) -> bool {
+    column >= rect.x
+        && column < rect.x.saturating_add(rect.width)
+        && row >= rect.y
+        && row < rect.y.saturating_add(rect.height)
+}
+
+/// Regions of the last rendered frame and what each one is.
+/// Rendering only borrows the app, so regions are pushed through a shared
+/// reference; clear the map at the start of every frame.
+pub struct HitMap<T> {
+    regions: RefCell<Vec<(Rect, T)>>,
+}
+
+impl<T: Clone> HitMap<T> {
+    pub fn new() -> Self {
+        Self {
+            regions: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.regions.borrow_mut().clear();
+    }
+
+    /// Record `target` at `rect`. Later regions sit on top of earlier ones,
+    /// so push in drawing order.
+    pub fn push(&self, rect: Rect, target: T) {
+        if rect.width > 0 && rect.height > 0 {
+            self.regions.borrow_mut().push((rect, target));
+        }
+    }
+
+    /// Record one region per visible row of a single-line-per-item list.
+    /// `area` is where the rows are drawn (inside any border), `offset` the
+    /// first item shown (`ListState::offset()` after rendering) and `len`
+    /// the item count; `target` maps an item index to its target, or `None`
+    /// for rows that can't be picked.
+    pub fn push_list(&self, area: Rect, offset: usize, len: usize, target: impl Fn(usize) -> Option<T>) {
+        for (line, index) in (offset..len).take(area.height as usize).enumerate() {
+            if let Some(target) = target(index) {
+                let row = Rect { y: area.y + line as u16, height: 1, ..area };
+                self.push(row, target);
+            }
+        }
+    }
+
+    /// The topmost target at `column`, `row`
+    pub fn hit(&self, column: u16, row: u16) -> Option<T> {
+        self.regions
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(rect, _)| rect_contains(*rect, column, row))
+            .map(|(_, target)| target.clone())
+    }
+}
+
+impl<T: Clone> Default for HitMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -53,11 +53,12 @@ impl Popup {
     }
 
     pub fn input(title: String, prompt: String, initial: String) -> Self {
+        let cursor_pos = initial.len(); // Start editing at the end of the initial text
         Self::new(PopupType::Input {
             title,
             prompt,
             input: initial,
-            cursor_pos: 0,
+            cursor_pos,
         })
     }
 
@@ -68,13 +69,86 @@ impl Popup {
     pub fn info(title: String, message: String) -> Self {
         Self::new(PopupType::Info { title, message })
     }
+
+    /// Current text of an input popup
+    pub fn input_value(&self) -> Option<&str> {
+        match &self.popup_type {
+            PopupType::Input { input, .. } => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Insert a character at the cursor of an input popup
+    pub fn insert_char(&mut self, c: char) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            let pos = (*cursor_pos).min(input.len());
+            input.insert(pos, c);
+            *cursor_pos = pos + c.len_utf8();
+        }
+    }
+
+    /// Delete the character before the cursor (Backspace)
+    pub fn delete_char_before_cursor(&mut self) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            if let Some((idx, _)) = input[..(*cursor_pos).min(input.len())].char_indices().next_back() {
+                input.remove(idx);
+                *cursor_pos = idx;
+            }
+        }
+    }
+
+    /// Delete the character under the cursor (Delete)
+    pub fn delete_char_at_cursor(&mut self) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            if *cursor_pos < input.len() {
+                input.remove(*cursor_pos);
+            }
+        }
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            if let Some((idx, _)) = input[..(*cursor_pos).min(input.len())].char_indices().next_back() {
+                *cursor_pos = idx;
+            }
+        }
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            if let Some(c) = input[(*cursor_pos).min(input.len())..].chars().next() {
+                *cursor_pos += c.len_utf8();
+            }
+        }
+    }
+
+    pub fn move_cursor_home(&mut self) {
+        if let PopupType::Input { cursor_pos, .. } = &mut self.popup_type {
+            *cursor_pos = 0;
+        }
+    }
+
+    pub fn move_cursor_end(&mut self) {
+        if let PopupType::Input { input, cursor_pos, .. } = &mut self.popup_type {
+            *cursor_pos = input.len();
+        }
+    }
+
+    /// Select a confirm popup's button (0 = Yes, 1 = No)
+    pub fn select_button(&mut self, index: usize) {
+        if let PopupType::Confirm { selected, .. } = &mut self.popup_type {
+            *selected = index.min(1);
+        }
+    }
 }
 
 /// Render popup with proper dimming
-/// Everything behind the popup should be dimmed to very dim grey
-pub fn render_popup(f: &mut Frame, area: Rect, popup: &Popup) {
+/// Everything behind the popup should be dimmed to very dim grey.
+/// Returns the screen area of each button drawn, for mouse hit-testing:
+/// Yes then No for a confirm popup, none for the others.
+pub fn render_popup(f: &mut Frame, area: Rect, popup: &Popup) -> Vec<Rect> {
     if !popup.visible {
-        return;
+        return Vec::new();
     }
 
     // Dim the entire background to very dim grey
@@ -85,7 +159,7 @@ pub fn render_popup(f: &mut Frame, area: Rect, popup: &Popup) {
 
     match &popup.popup_type {
         PopupType::Confirm { title, message, selected } => {
-            render_confirm_popup(f, area, title, message, *selected);
+            return render_confirm_popup(f, area, title, message, *selected);
         }
         PopupType::Input { title, prompt, input, cursor_pos } => {
             render_input_popup(f, area, title, prompt, input, *cursor_pos);
@@ -97,40 +171,11 @@ pub fn render_popup(f: &mut Frame, area: Rect, popup: &Popup) {
             render_info_popup(f, area, title, message);
         }
     }
+    Vec::new()
 }
 
-fn render_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, selected: usize) {
-    // Wrap message text
-    let max_text_width = 50;
-    let wrapped_lines = wrap_text(message, max_text_width);
-    
-    // Calculate popup dimensions
-    let max_line_len = wrapped_lines.iter()
-        .map(|l| l.len())
-        .max()
-        .unwrap_or(title.len())
-        .max(title.len())
-        .max(30);
-    
-    let popup_width = (max_line_len as u16 + 8)
-        .max(40)
-        .min((area.width as f32 * 0.60) as u16)
-        .min(area.width.saturating_sub(4)); // Ensure at least 2 chars margin on each side
-    
-    let popup_height = (wrapped_lines.len() as u16 + 7)
-        .min(area.height.saturating_sub(4)); // Ensure at least 2 lines margin top/bottom
-    
-    // Calculate centered position manually to ensure it fits
-    let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
-    let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
-    
-    // Ensure popup doesn't go outside bounds
-    let popup_area = Rect {
-        x: popup_x.max(area.x).min(area.x + area.width.saturating_sub(popup_width)),
-        y: popup_y.max(area.y).min(area.y + area.height.saturating_sub(popup_height)),
-        width: popup_width.min(area.width.saturating_sub((popup_x.max(area.x) - area.x))),
-        height: popup_height.min(area.height.saturating_sub((popup_y.max(area.y) - area.y))),
-    };
+fn render_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, selected: usize) -> Vec<Rect> {
+    let (popup_area, wrapped_lines) = confirm_popup_layout(area, title, message);
     
     // Use the actual popup area width for rendering (not the calculated width)
     let actual_width = popup_area.width as usize;
@@ -200,12 +245,22 @@ fn render_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, s
     
     let mut button_spans = vec![Span::styled("┃", Style::default().fg(Color::White))];
     button_spans.push(Span::raw(" ".repeat(left_pad)));
+    // (span index, button) of the buttons, to find them on screen afterwards
+    let yes_span = button_spans.len();
     button_spans.push(Span::styled(yes_text, yes_style));
     button_spans.push(Span::raw("  "));
+    let no_span = button_spans.len();
     button_spans.push(Span::styled(no_text, no_style));
     button_spans.push(Span::raw(" ".repeat(right_pad)));
     button_spans.push(Span::styled("┃", Style::default().fg(Color::White)));
     
+    let button_row = popup_lines.len() as u16;
+    let button_columns: Vec<(u16, u16)> = [yes_span, no_span].iter()
+        .map(|&index| {
+            let offset: usize = button_spans[..index].iter().map(|span| span.width()).sum();
+            (offset as u16, button_spans[index].width() as u16)
+        })
+        .collect();
     popup_lines.push(Line::from(button_spans));
     
     // Empty line - use actual width
@@ -233,6 +288,54 @@ fn render_confirm_popup(f: &mut Frame, area: Rect, title: &str, message: &str, s
         .style(Style::default().bg(hex_color(0x141420))); // Panel background color
     
     f.render_widget(popup_widget, final_popup_area);
+    
+    if button_row >= final_popup_area.height {
+        return Vec::new();
+    }
+    let right = final_popup_area.x + final_popup_area.width;
+    button_columns.into_iter()
+        .map(|(offset, width)| {
+            let x = (final_popup_area.x + offset).min(right);
+            Rect { x, y: final_popup_area.y + button_row, width: (x + width).min(right) - x, height: 1 }
+        })
+        .collect()
+}
+
+/// Where a confirm popup goes on `area`, and its message wrapped to fit
+fn confirm_popup_layout(area: Rect, title: &str, message: &str) -> (Rect, Vec<String>) {
+    // Wrap message text
+    let max_text_width = 50;
+    let wrapped_lines = wrap_text(message, max_text_width);
+    
+    // Calculate popup dimensions
+    let max_line_len = wrapped_lines.iter()
+        .map(|l| l.len())
+        .max()
+        .unwrap_or(title.len())
+        .max(title.len())
+        .max(30);
+    
+    let popup_width = (max_line_len as u16 + 8)
+        .max(40)
+        .min((area.width as f32 * 0.60) as u16)
+        .min(area.width.saturating_sub(4)); // Ensure at least 2 chars margin on each side
+    
+    let popup_height = (wrapped_lines.len() as u16 + 7)
+        .min(area.height.saturating_sub(4)); // Ensure at least 2 lines margin top/bottom
+    
+    // Calculate centered position manually to ensure it fits
+    let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+    
+    // Ensure popup doesn't go outside bounds
+    let popup_area = Rect {
+        x: popup_x.max(area.x).min(area.x + area.width.saturating_sub(popup_width)),
+        y: popup_y.max(area.y).min(area.y + area.height.saturating_sub(popup_height)),
+        width: popup_width.min(area.width.saturating_sub((popup_x.max(area.x) - area.x))),
+        height: popup_height.min(area.height.saturating_sub((popup_y.max(area.y) - area.y))),
+    };
+    
+    (popup_area, wrapped_lines)
 }
 
 fn render_input_popup(f: &mut Frame, area: Rect, title: &str, prompt: &str, input: &str, cursor_pos: usize) {
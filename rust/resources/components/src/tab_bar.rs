@@ -61,10 +61,12 @@ impl TabBar {
         self
     }
 
-    pub fn render(&self, f: &mut Frame) {
+    /// Draw the bar and return the screen area of each tab drawn, in item
+    /// order, for mouse hit-testing. Tabs that don't fit are left out.
+    pub fn render(&self, f: &mut Frame) -> Vec<Rect> {
         let area = self.calculate_area(f.area());
         if area.width == 0 || area.height == 0 {
-            return;
+            return Vec::new();
         }
 
         // Render the decorative line above the tab bar (only for Tab style)
@@ -90,11 +92,20 @@ impl TabBar {
 
         // Use the estimated width, not the area width, to ensure all tabs are shown
         let estimated_width = self.estimate_width();
-        let line = self.build_tab_line(estimated_width.max(area.width));
+        let (line, tabs) = self.layout_tab_line(estimated_width.max(area.width));
         let paragraph = Paragraph::new(line);
         f.render_widget(paragraph, area);
+
+        // The line is clipped to the bar's area
+        let right = area.x + area.width;
+        tabs.into_iter()
+            .map(|(offset, width)| {
+                let x = (area.x + offset).min(right);
+                Rect { x, y: area.y, width: (x + width).min(right) - x, height: area.height }
+            })
+            .collect()
     }
-    
+
     fn build_top_line(&self, tab_area: Rect, _active_tab: &TabBarItem) -> Line<'static> {
         let mut spans = Vec::new();
         
@@ -324,7 +335,14 @@ impl TabBar {
     }
 
     pub fn build_tab_line(&self, max_width: u16) -> Line<'static> {
+        self.layout_tab_line(max_width).0
+    }
+
+    /// The tab line and, for each tab that fits, its (offset from the start
+    /// of the line, width)
+    fn layout_tab_line(&self, max_width: u16) -> (Line<'static>, Vec<(u16, u16)>) {
         let mut spans = Vec::new();
+        let mut tabs = Vec::new();
         let mut current_width = 0;
 
         // Check if first tab is active to determine leading separator
@@ -402,6 +420,7 @@ impl TabBar {
             };
 
             spans.push(Span::styled(tab_text, style));
+            tabs.push((current_width, tab_width));
             current_width += tab_width;
             
             // Add separator after active tab if there's a next tab
@@ -427,7 +446,7 @@ impl TabBar {
             spans.push(Span::styled("──", Style::default().fg(Color::White)));
         }
 
-        Line::from(spans)
+        (Line::from(spans), tabs)
     }
 }
 